@@ -1,4 +1,5 @@
 use crate::Market;
+use crate::book::{FeeSchedule, OrderLimits};
 use mosaic_miden::Network;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -14,6 +15,10 @@ pub enum AccountOrder {
         network: Network,
         market: Market,
         owner_account: String,
+        #[serde(default)]
+        fees: FeeSchedule,
+        #[serde(default)]
+        limits: OrderLimits,
     },
     CreateFaucet {
         network: Network,
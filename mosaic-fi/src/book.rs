@@ -0,0 +1,1192 @@
+use crate::Market;
+use crate::note::{Amount, Price, Scale, Side, UUID};
+use miden_objects::{
+    Felt, Word,
+    account::{Account, AccountId, NetworkId},
+    address::{AccountIdAddress, Address, AddressInterface},
+};
+use serde::{Deserialize, Serialize};
+
+// Storage layout of the desk account, mirroring `book.masm`
+pub const CONFIG_BASE_SLOT: u8 = 1;
+pub const CONFIG_QUOTE_SLOT: u8 = 2;
+pub const CONFIG_STATUS_SLOT: u8 = 4;
+pub const OFFSET_SELL: u8 = 5;
+pub const OFFSET_BUY: u8 = 10;
+pub const COUNTER_OFFSET: u8 = 0;
+pub const BOOK_OFFSET: u8 = 1;
+pub const BOOK_HEAD_OFFSET: u8 = 2;
+pub const BOOK_INTENTS_OFFSET: u8 = 4;
+pub const FILLS_COUNTER_SLOT: u8 = 15;
+pub const FILLS_SLOT: u8 = 16;
+pub const CONFIG_OWNER_SLOT: u8 = 17;
+pub const CONFIG_FEES_SLOT: u8 = 18;
+pub const CONFIG_LIMITS_SLOT: u8 = 19;
+
+const MAX_FEE_BPS: u16 = 10_000;
+
+const FILL_FIELD_TRADE: u64 = 0;
+const FILL_FIELD_TAKER_INTENT: u64 = 1;
+const FILL_FIELD_MAKER_INTENT: u64 = 2;
+const FILL_FIELD_MAKER_SENDER: u64 = 3;
+const FILL_FIELD_TAKER_SENDER: u64 = 4;
+const FILL_FIELD_FEES: u64 = 5;
+
+const ENTRY_FIELD_LINKS: u64 = 0;
+const ENTRY_FIELD_INTENT: u64 = 1;
+const ENTRY_FIELD_SENDER: u64 = 2;
+const ENTRY_FIELD_EXPIRY: u64 = 3;
+const ENTRY_FIELD_BACKED: u64 = 4;
+
+/// Trading status of a desk, stored in its status config slot
+///
+/// Only an open desk accepts new offers and quote requests, resting offers
+/// can be cancelled whatever the status.
+#[derive(
+    PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema, Debug, Clone, Copy, Default,
+)]
+pub enum DeskStatus {
+    /// Not trading, the status of a desk that was never opened
+    #[default]
+    Closed,
+    Open,
+    /// Temporarily halted
+    Paused,
+    /// Winding down, only cancels are accepted
+    CancelOnly,
+}
+
+impl DeskStatus {
+    /// Code stored in the status slot
+    pub fn code(self) -> u64 {
+        match self {
+            DeskStatus::Closed => 0,
+            DeskStatus::Open => 1,
+            DeskStatus::Paused => 2,
+            DeskStatus::CancelOnly => 3,
+        }
+    }
+
+    pub fn from_code(code: u64) -> Result<Self, Box<dyn std::error::Error>> {
+        match code {
+            0 => Ok(DeskStatus::Closed),
+            1 => Ok(DeskStatus::Open),
+            2 => Ok(DeskStatus::Paused),
+            3 => Ok(DeskStatus::CancelOnly),
+            _ => Err(format!("Invalid desk status code {}", code).into()),
+        }
+    }
+
+    /// Status word passed to `update_status`, as note inputs
+    pub fn to_word(self) -> [u64; 4] {
+        [self.code(), 0, 0, 0]
+    }
+
+    /// Status slot value, to build a desk with this status
+    pub fn storage_word(self) -> Word {
+        Word::from(self.to_word().map(Felt::new))
+    }
+
+    pub fn accepts_offers(self) -> bool {
+        self == DeskStatus::Open
+    }
+}
+
+/// Maker and taker fees of a desk in basis points, stored in its fees config slot
+#[derive(
+    PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema, Debug, Clone, Copy, Default,
+)]
+pub struct FeeSchedule {
+    /// Fee on the resting offer of a fill, reported but not collected
+    pub maker_bps: u16,
+    /// Fee on the incoming offer of a fill, reported but not collected, and
+    /// on the client of a settlement, collected with its payment
+    pub taker_bps: u16,
+}
+
+impl FeeSchedule {
+    pub fn check(self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.maker_bps > MAX_FEE_BPS || self.taker_bps > MAX_FEE_BPS {
+            return Err(format!("Fees cannot exceed {} bps", MAX_FEE_BPS).into());
+        }
+        Ok(())
+    }
+
+    /// Maker fee on a notional, in quote units rounded up
+    pub fn maker_fee(self, notional: u64) -> u64 {
+        bps_fee(notional, self.maker_bps)
+    }
+
+    /// Taker fee on a notional, in quote units rounded up
+    pub fn taker_fee(self, notional: u64) -> u64 {
+        bps_fee(notional, self.taker_bps)
+    }
+
+    /// Fees slot value, to build a desk with this schedule
+    pub fn storage_word(self) -> Word {
+        Word::from([
+            Felt::new(self.maker_bps as u64),
+            Felt::new(self.taker_bps as u64),
+            Felt::new(0),
+            Felt::new(0),
+        ])
+    }
+}
+
+fn bps_fee(notional: u64, bps: u16) -> u64 {
+    let fee = (notional as u128 * bps as u128).div_ceil(MAX_FEE_BPS as u128);
+    u64::try_from(fee).unwrap_or(u64::MAX)
+}
+
+/// Order size limits of a desk, stored in its limits config slot
+///
+/// A zero limit is not enforced, the default accepts any offer.
+#[derive(
+    PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema, Debug, Clone, Copy, Default,
+)]
+pub struct OrderLimits {
+    /// Prices must be a multiple of the tick size
+    #[serde(default)]
+    pub tick_size: Price,
+    /// Amounts must be a multiple of the lot size
+    #[serde(default)]
+    pub lot_size: Amount,
+    /// Smallest notional of an offer, in quote units
+    #[serde(default)]
+    pub min_notional: u64,
+    /// Largest notional of an offer, in quote units
+    #[serde(default)]
+    pub max_notional: u64,
+}
+
+impl OrderLimits {
+    /// Check an offer against the limits, as `book.masm` does on insertion
+    pub fn check(
+        self,
+        amount: Amount,
+        price: Price,
+        scale: Scale,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let tick = self.tick_size.units();
+        if tick != 0 && !price.units().is_multiple_of(tick) {
+            return Err(format!(
+                "Price {} is not a multiple of the desk tick size {}",
+                price, tick
+            )
+            .into());
+        }
+        let lot = self.lot_size.units();
+        if lot != 0 && !amount.units().is_multiple_of(lot) {
+            return Err(format!(
+                "Amount {} is not a multiple of the desk lot size {}",
+                amount, lot
+            )
+            .into());
+        }
+
+        // Compare amount * price with the bounds scaled by the base unit, the
+        // rounding of the notional must not let an offer through the contract
+        // would refuse
+        let one = base_unit(scale)?;
+        let product = amount.units() as u128 * price.units() as u128;
+        if self.min_notional != 0 && product < self.min_notional as u128 * one {
+            return Err(format!(
+                "Offer notional is below the desk minimum of {}",
+                self.min_notional
+            )
+            .into());
+        }
+        if self.max_notional != 0 && product > self.max_notional as u128 * one {
+            return Err(format!(
+                "Offer notional is above the desk maximum of {}",
+                self.max_notional
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Limits slot value, to build a desk with these limits
+    ///
+    /// The notional bounds are stored multiplied by the base unit and must
+    /// still fit in a field element.
+    pub fn storage_word(self, scale: Scale) -> Result<Word, Box<dyn std::error::Error>> {
+        if self.max_notional != 0 && self.max_notional < self.min_notional {
+            return Err("Maximum notional is below the minimum notional".into());
+        }
+        let one = base_unit(scale)?;
+        let scaled = |notional: u64| {
+            u64::try_from(notional as u128 * one)
+                .ok()
+                .and_then(|raw| Felt::try_from(raw).ok())
+                .ok_or_else(|| format!("Notional limit {} is too large", notional))
+        };
+        let felt =
+            |units: u64| Felt::try_from(units).map_err(|_| format!("Limit {} is too large", units));
+
+        Ok(Word::from([
+            felt(self.tick_size.units())?,
+            felt(self.lot_size.units())?,
+            scaled(self.min_notional)?,
+            scaled(self.max_notional)?,
+        ]))
+    }
+}
+
+fn base_unit(scale: Scale) -> Result<u128, Box<dyn std::error::Error>> {
+    Ok(10u128
+        .checked_pow(scale.base_decimals as u32)
+        .ok_or("Too many base decimals")?)
+}
+
+/// Base unit slot value, to build a desk for a market with this scale
+///
+/// The desk needs it to check and refund the quote notional backing buy offers.
+pub fn base_unit_word(scale: Scale) -> Result<Word, Box<dyn std::error::Error>> {
+    let one =
+        Felt::try_from(u64::try_from(base_unit(scale)?)?).map_err(|_| "Base unit is too large")?;
+    Ok(Word::from([one, Felt::new(0), Felt::new(0), Felt::new(0)]))
+}
+
+/// An offer resting in the desk book
+#[derive(PartialEq, Serialize, Deserialize, schemars::JsonSchema, Debug, Clone)]
+pub struct BookEntry {
+    /// Book entry id
+    pub id: u64,
+    pub price: Price,
+    /// Remaining amount
+    pub amount: Amount,
+    /// Intent of the offer
+    pub uuid: UUID,
+    /// Account that placed the offer, in bech32 format
+    pub account: String,
+    /// Block at which the offer expires, if any
+    pub expiry_block: Option<u32>,
+    /// Whether the desk holds the offered asset, refunded if the offer does not fill
+    #[serde(default)]
+    pub backed: bool,
+    /// Price in quote tokens per base token, when the market decimals are known
+    #[serde(default)]
+    pub decimal_price: Option<String>,
+    /// Remaining amount in base tokens, when the market decimals are known
+    #[serde(default)]
+    pub decimal_amount: Option<String>,
+}
+
+impl BookEntry {
+    pub fn is_expired(&self, block_num: u32) -> bool {
+        self.expiry_block
+            .is_some_and(|expiry_block| expiry_block <= block_num)
+    }
+
+    /// Format the price and amount with the decimals of the market
+    pub fn with_decimals(mut self, scale: Scale) -> Self {
+        self.decimal_price = Some(self.price.to_decimal(scale));
+        self.decimal_amount = Some(self.amount.to_decimal(scale));
+        self
+    }
+}
+
+/// Total amount offered at a price
+#[derive(PartialEq, Serialize, Deserialize, schemars::JsonSchema, Debug, Clone)]
+pub struct PriceLevel {
+    pub price: Price,
+    pub amount: Amount,
+    /// Number of offers at this price
+    pub orders: usize,
+}
+
+/// Both sides of a desk book, best price first
+#[derive(PartialEq, Serialize, Deserialize, schemars::JsonSchema, Debug, Clone, Default)]
+pub struct OrderBook {
+    /// Buy side, highest price first
+    pub bids: Vec<BookEntry>,
+    /// Sell side, lowest price first
+    pub asks: Vec<BookEntry>,
+}
+
+impl OrderBook {
+    /// Buy side aggregated by price
+    pub fn bid_levels(&self) -> Vec<PriceLevel> {
+        aggregate(&self.bids)
+    }
+
+    /// Sell side aggregated by price
+    pub fn ask_levels(&self) -> Vec<PriceLevel> {
+        aggregate(&self.asks)
+    }
+
+    /// The book without the offers expired at the given block
+    ///
+    /// Expired offers stay in storage until the desk prunes them.
+    pub fn live_at(mut self, block_num: u32) -> Self {
+        self.bids.retain(|entry| !entry.is_expired(block_num));
+        self.asks.retain(|entry| !entry.is_expired(block_num));
+        self
+    }
+
+    /// Format the prices and amounts of the offers with the decimals of the market
+    pub fn with_decimals(mut self, scale: Scale) -> Self {
+        self.bids = self
+            .bids
+            .into_iter()
+            .map(|entry| entry.with_decimals(scale))
+            .collect();
+        self.asks = self
+            .asks
+            .into_iter()
+            .map(|entry| entry.with_decimals(scale))
+            .collect();
+        self
+    }
+}
+
+/// Aggregate entries sorted by price into levels, keeping the order
+fn aggregate(entries: &[BookEntry]) -> Vec<PriceLevel> {
+    let mut levels: Vec<PriceLevel> = Vec::new();
+    for entry in entries {
+        match levels.last_mut() {
+            Some(level) if level.price == entry.price => {
+                level.amount = level.amount.saturating_add(entry.amount);
+                level.orders += 1;
+            }
+            _ => levels.push(PriceLevel {
+                price: entry.price,
+                amount: entry.amount,
+                orders: 1,
+            }),
+        }
+    }
+    levels
+}
+
+/// A trade executed by the desk when an incoming offer crossed the book
+#[derive(PartialEq, Serialize, Deserialize, schemars::JsonSchema, Debug, Clone)]
+pub struct Fill {
+    /// Sequence number of the fill, starting at 1
+    pub id: u64,
+    /// Side of the incoming offer
+    pub side: Side,
+    /// Book entry id of the resting offer
+    pub maker_id: u64,
+    /// Execution price, taken from the resting offer
+    pub price: Price,
+    /// Filled amount
+    pub amount: Amount,
+    /// Intent of the incoming offer
+    pub taker_uuid: UUID,
+    /// Intent of the resting offer
+    pub maker_uuid: UUID,
+    /// Account that placed the resting offer, in bech32 format
+    pub maker_account: String,
+    /// Account that sent the incoming offer, in bech32 format, unknown for
+    /// fills recorded by desks deployed before takers were recorded
+    #[serde(default)]
+    pub taker_account: Option<String>,
+    /// Fee schedule in effect when the fill executed, unknown for fills
+    /// recorded by desks deployed before fee schedules were recorded
+    #[serde(default)]
+    pub fees: Option<FeeSchedule>,
+    /// Fee owed by the resting offer, in quote units, informational only
+    #[serde(default)]
+    pub maker_fee: u64,
+    /// Fee owed by the incoming offer, in quote units, informational only
+    #[serde(default)]
+    pub taker_fee: u64,
+    /// Execution price in quote tokens per base token, when the market decimals are known
+    #[serde(default)]
+    pub decimal_price: Option<String>,
+    /// Filled amount in base tokens, when the market decimals are known
+    #[serde(default)]
+    pub decimal_amount: Option<String>,
+}
+
+impl Fill {
+    /// Charge the desk fees on the notional of the fill
+    ///
+    /// Book fills move no fee on chain, the fees are only accounted for off
+    /// chain with the schedule recorded with the fill, or the given one for
+    /// fills recorded without.
+    pub fn charge_fees(
+        &mut self,
+        fees: FeeSchedule,
+        scale: Scale,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let fees = self.fees.unwrap_or(fees);
+        let notional = self.price.notional(self.amount, scale)?;
+        self.maker_fee = fees.maker_fee(notional);
+        self.taker_fee = fees.taker_fee(notional);
+        Ok(())
+    }
+
+    /// Format the price and amount with the decimals of the market
+    pub fn with_decimals(mut self, scale: Scale) -> Self {
+        self.decimal_price = Some(self.price.to_decimal(scale));
+        self.decimal_amount = Some(self.amount.to_decimal(scale));
+        self
+    }
+}
+
+fn field_key(field: u64, id: u64) -> Word {
+    Word::from([Felt::new(id), Felt::new(field), Felt::new(0), Felt::new(0)])
+}
+
+pub(crate) fn decode_side(value: Felt) -> Result<Side, Box<dyn std::error::Error>> {
+    match value.as_int() {
+        0 => Ok(Side::SELL),
+        1 => Ok(Side::BUY),
+        other => Err(format!("Invalid side {} in desk storage", other).into()),
+    }
+}
+
+pub(crate) fn decode_uuid(word: Word) -> UUID {
+    ((word[0].as_int() as u128) << 64) | word[1].as_int() as u128
+}
+
+/// Decode an account id stored as [zero, zero, prefix, suffix] from the top of the stack
+pub fn decode_account_id(word: Word) -> Result<AccountId, Box<dyn std::error::Error>> {
+    Ok(AccountId::try_from([word[1], word[0]])?)
+}
+
+/// Encode an account id in bech32 format for the network of the desk
+fn encode_account_id(account_id: AccountId, network_id: NetworkId) -> String {
+    Address::from(AccountIdAddress::new(
+        account_id,
+        AddressInterface::Unspecified,
+    ))
+    .to_bech32(network_id)
+}
+
+/// Decode a currency faucet stored as [symbol, zero, prefix, suffix] in the desk configuration
+fn decode_currency_faucet(word: Word) -> Result<AccountId, Box<dyn std::error::Error>> {
+    Ok(AccountId::try_from([word[2], word[3]])?)
+}
+
+/// Faucet ids of the base and quote currencies traded by the desk
+pub fn read_market_faucets(
+    account: &Account,
+) -> Result<(AccountId, AccountId), Box<dyn std::error::Error>> {
+    let storage = account.storage();
+    Ok((
+        decode_currency_faucet(storage.get_item(CONFIG_BASE_SLOT)?)?,
+        decode_currency_faucet(storage.get_item(CONFIG_QUOTE_SLOT)?)?,
+    ))
+}
+
+/// Check that the desk trades the given market
+pub fn check_market(account: &Account, market: &Market) -> Result<(), Box<dyn std::error::Error>> {
+    if read_market_faucets(account)? != market.faucet_ids()? {
+        return Err(format!(
+            "Market {} is not traded by desk {}",
+            market,
+            account.id().to_hex()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn uuid_key(uuid: UUID) -> Word {
+    Word::from([
+        Felt::new((uuid >> 64) as u64),
+        Felt::new(uuid as u64),
+        Felt::new(0),
+        Felt::new(0),
+    ])
+}
+
+/// Storage slot of a book structure on the given side
+pub fn side_slot(side: Side, offset: u8) -> u8 {
+    match side {
+        Side::SELL => OFFSET_SELL + offset,
+        Side::BUY => OFFSET_BUY + offset,
+    }
+}
+
+/// Book entry id of the offer resting under the given intent, if any
+pub fn entry_id_by_uuid(
+    account: &Account,
+    side: Side,
+    uuid: UUID,
+) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+    // Stored as [zero, zero, used, id] from the top of the stack
+    let index = account
+        .storage()
+        .get_map_item(side_slot(side, BOOK_INTENTS_OFFSET), uuid_key(uuid))?;
+    let id = index[0].as_int();
+    Ok((id != 0).then_some(id))
+}
+
+/// Account that placed the book entry, if the entry is resting
+pub fn entry_sender(
+    account: &Account,
+    side: Side,
+    id: u64,
+) -> Result<Option<AccountId>, Box<dyn std::error::Error>> {
+    let sender = account.storage().get_map_item(
+        side_slot(side, BOOK_OFFSET),
+        field_key(ENTRY_FIELD_SENDER, id),
+    )?;
+    if sender == Word::default() {
+        return Ok(None);
+    }
+    Ok(Some(decode_account_id(sender)?))
+}
+
+/// Whether the intent was already offered on the given side
+pub fn is_uuid_used(
+    account: &Account,
+    side: Side,
+    uuid: UUID,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let index = account
+        .storage()
+        .get_map_item(side_slot(side, BOOK_INTENTS_OFFSET), uuid_key(uuid))?;
+    Ok(index[1].as_int() != 0)
+}
+
+/// Read both sides of the desk book, with accounts encoded for the network of the desk
+pub fn read_book(
+    account: &Account,
+    network_id: NetworkId,
+) -> Result<OrderBook, Box<dyn std::error::Error>> {
+    Ok(OrderBook {
+        bids: read_side(account, Side::BUY, network_id)?,
+        asks: read_side(account, Side::SELL, network_id)?,
+    })
+}
+
+/// Read the account allowed to administer a desk
+pub fn read_owner(account: &Account) -> Result<AccountId, Box<dyn std::error::Error>> {
+    decode_account_id(account.storage().get_item(CONFIG_OWNER_SLOT)?)
+}
+
+/// Read the fee schedule of a desk
+pub fn read_fees(account: &Account) -> Result<FeeSchedule, Box<dyn std::error::Error>> {
+    decode_fees(account.storage().get_item(CONFIG_FEES_SLOT)?)
+}
+
+/// Decode a fee schedule stored as [zero, zero, taker_bps, maker_bps] from the top of the stack
+fn decode_fees(word: Word) -> Result<FeeSchedule, Box<dyn std::error::Error>> {
+    Ok(FeeSchedule {
+        maker_bps: u16::try_from(word[0].as_int())?,
+        taker_bps: u16::try_from(word[1].as_int())?,
+    })
+}
+
+/// Read the order limits of a desk, built with the same scale
+pub fn read_limits(
+    account: &Account,
+    scale: Scale,
+) -> Result<OrderLimits, Box<dyn std::error::Error>> {
+    let word = account.storage().get_item(CONFIG_LIMITS_SLOT)?;
+    let one = base_unit(scale)?;
+    let notional = |felt: Felt| u64::try_from(felt.as_int() as u128 / one).unwrap_or(u64::MAX);
+    Ok(OrderLimits {
+        tick_size: Price::new(word[0].as_int()),
+        lot_size: Amount::new(word[1].as_int()),
+        min_notional: notional(word[2]),
+        max_notional: notional(word[3]),
+    })
+}
+
+/// Read the trading status of a desk
+pub fn read_status(account: &Account) -> Result<DeskStatus, Box<dyn std::error::Error>> {
+    let word = account.storage().get_item(CONFIG_STATUS_SLOT)?;
+    if word.as_elements()[1..]
+        .iter()
+        .any(|felt| felt.as_int() != 0)
+    {
+        return Err("Invalid desk status word".into());
+    }
+    DeskStatus::from_code(word[0].as_int())
+}
+
+/// Walk the linked list of one side of the desk book from its head
+pub fn read_side(
+    account: &Account,
+    side: Side,
+    network_id: NetworkId,
+) -> Result<Vec<BookEntry>, Box<dyn std::error::Error>> {
+    let storage = account.storage();
+    let book_slot = side_slot(side, BOOK_OFFSET);
+
+    // Entry ids are never reused, so the list cannot be longer than the counter
+    let count = storage.get_item(side_slot(side, COUNTER_OFFSET))?[3].as_int();
+    let mut current = storage.get_item(side_slot(side, BOOK_HEAD_OFFSET))?[0].as_int();
+
+    let mut entries = Vec::new();
+    while current != 0 {
+        if entries.len() as u64 >= count {
+            return Err(format!("Desk book {:?} side is not a list", side).into());
+        }
+
+        // Stored as [previous_id, next_id, price, amount] from the top of the stack
+        let links = storage.get_map_item(book_slot, field_key(ENTRY_FIELD_LINKS, current))?;
+        let intent = storage.get_map_item(book_slot, field_key(ENTRY_FIELD_INTENT, current))?;
+        let sender = storage.get_map_item(book_slot, field_key(ENTRY_FIELD_SENDER, current))?;
+        let expiry = storage.get_map_item(book_slot, field_key(ENTRY_FIELD_EXPIRY, current))?;
+        let backed = storage.get_map_item(book_slot, field_key(ENTRY_FIELD_BACKED, current))?;
+        let expiry_block = u32::try_from(expiry[0].as_int())?;
+
+        entries.push(BookEntry {
+            id: current,
+            price: Price::new(links[1].as_int()),
+            amount: Amount::new(links[0].as_int()),
+            uuid: decode_uuid(intent),
+            account: encode_account_id(decode_account_id(sender)?, network_id),
+            expiry_block: (expiry_block != 0).then_some(expiry_block),
+            backed: backed[0].as_int() != 0,
+            decimal_price: None,
+            decimal_amount: None,
+        });
+        current = links[2].as_int();
+    }
+
+    Ok(entries)
+}
+
+/// Number of fills recorded by the desk
+pub fn fill_count(account: &Account) -> Result<u64, Box<dyn std::error::Error>> {
+    let counter = account.storage().get_item(FILLS_COUNTER_SLOT)?;
+    Ok(counter[3].as_int())
+}
+
+/// Read the fills recorded by the desk after the given fill id, with accounts
+/// encoded for the network of the desk
+pub fn read_fills(
+    account: &Account,
+    after: u64,
+    network_id: NetworkId,
+) -> Result<Vec<Fill>, Box<dyn std::error::Error>> {
+    let count = fill_count(account)?;
+    let storage = account.storage();
+
+    let mut fills = Vec::new();
+    for id in (after + 1)..=count {
+        // Stored as [maker_id, taker_side, price, quantity] from the top of the stack
+        let trade = storage.get_map_item(FILLS_SLOT, field_key(FILL_FIELD_TRADE, id))?;
+        let taker_intent =
+            storage.get_map_item(FILLS_SLOT, field_key(FILL_FIELD_TAKER_INTENT, id))?;
+        let maker_intent =
+            storage.get_map_item(FILLS_SLOT, field_key(FILL_FIELD_MAKER_INTENT, id))?;
+        let maker_sender =
+            storage.get_map_item(FILLS_SLOT, field_key(FILL_FIELD_MAKER_SENDER, id))?;
+        let taker_sender =
+            storage.get_map_item(FILLS_SLOT, field_key(FILL_FIELD_TAKER_SENDER, id))?;
+        let fees = storage.get_map_item(FILLS_SLOT, field_key(FILL_FIELD_FEES, id))?;
+
+        // Desks recording the taker also record the fee schedule
+        let (taker_account, fees) = if taker_sender == Word::default() {
+            (None, None)
+        } else {
+            (
+                Some(encode_account_id(
+                    decode_account_id(taker_sender)?,
+                    network_id,
+                )),
+                Some(decode_fees(fees)?),
+            )
+        };
+
+        fills.push(Fill {
+            id,
+            side: decode_side(trade[2])?,
+            maker_id: trade[3].as_int(),
+            price: Price::new(trade[1].as_int()),
+            amount: Amount::new(trade[0].as_int()),
+            taker_uuid: decode_uuid(taker_intent),
+            maker_uuid: decode_uuid(maker_intent),
+            maker_account: encode_account_id(decode_account_id(maker_sender)?, network_id),
+            taker_account,
+            fees,
+            maker_fee: 0,
+            taker_fee: 0,
+            decimal_price: None,
+            decimal_amount: None,
+        });
+    }
+
+    Ok(fills)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::note::{Order, TimeInForce, compile_note_from_account_id};
+    use crate::testing::{
+        MOCK_FEES, consume, mock_desk, mock_desk_with_limits, mock_market, to_bech32, to_note,
+        update_status,
+    };
+    use miden_client::testing::MockChain;
+    use miden_objects::testing::account_id::ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE;
+
+    const SELL_BOOK_SLOT: u8 = 6;
+    const SELL_HEAD_SLOT: u8 = 7;
+    const BUY_HEAD_SLOT: u8 = 12;
+
+    fn try_offer(
+        chain: &MockChain,
+        desk: &mut Account,
+        sender: AccountId,
+        offer: (UUID, Side, u64, u64),
+    ) -> Result<(), String> {
+        place(chain, desk, sender, offer, TimeInForce::GTC)
+    }
+
+    fn place(
+        chain: &MockChain,
+        desk: &mut Account,
+        sender: AccountId,
+        (uuid, side, amount, price): (UUID, Side, u64, u64),
+        time_in_force: TimeInForce,
+    ) -> Result<(), String> {
+        let order = Order::LiquidityOffer {
+            market: mock_market().0,
+            uuid,
+            side,
+            amount: amount.into(),
+            price: price.into(),
+            time_in_force,
+        };
+        let mosaic_note = compile_note_from_account_id(sender, order).unwrap();
+        consume(chain, desk, to_note(&mosaic_note))
+    }
+
+    fn offer(
+        chain: &MockChain,
+        desk: &mut Account,
+        sender: AccountId,
+        offer: (UUID, Side, u64, u64),
+    ) {
+        try_offer(chain, desk, sender, offer).unwrap();
+    }
+
+    fn head(desk: &Account, slot: u8) -> u64 {
+        desk.storage().get_item(slot).unwrap()[0].as_int()
+    }
+
+    fn cancel(
+        chain: &MockChain,
+        desk: &mut Account,
+        sender: AccountId,
+        (uuid, side, entry_id): (UUID, Side, Option<u64>),
+    ) -> Result<(), String> {
+        let order = Order::CancelOffer {
+            market: mock_market().0,
+            uuid,
+            side,
+            entry_id,
+        };
+        let mosaic_note = compile_note_from_account_id(sender, order).unwrap();
+        consume(chain, desk, to_note(&mosaic_note))
+    }
+
+    /// Walk the sell side, checking the back pointers along the way
+    fn sell_ids(desk: &Account) -> Vec<u64> {
+        let mut ids = Vec::new();
+        let mut previous = 0;
+        let mut current = head(desk, SELL_HEAD_SLOT);
+        while current != 0 {
+            let id_key = Word::from([Felt::new(current), Felt::new(0), Felt::new(0), Felt::new(0)]);
+            let entry = desk.storage().get_map_item(SELL_BOOK_SLOT, id_key).unwrap();
+            assert_eq!(entry[3].as_int(), previous);
+            ids.push(current);
+            previous = current;
+            current = entry[2].as_int();
+        }
+        ids
+    }
+
+    #[test]
+    fn test_crossing_offers_fill_at_price_time_priority() {
+        let (chain, mut desk, sender) = mock_desk();
+
+        offer(&chain, &mut desk, sender, (1, Side::SELL, 5, 100));
+        offer(&chain, &mut desk, sender, (2, Side::SELL, 5, 100));
+        offer(&chain, &mut desk, sender, (3, Side::SELL, 5, 110));
+        assert_eq!(fill_count(&desk).unwrap(), 0);
+
+        // Takes the first offer at 100, then part of the second one
+        let taker = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+        offer(&chain, &mut desk, taker, (4, Side::BUY, 7, 105));
+        let fills = read_fills(&desk, 0, NetworkId::Testnet).unwrap();
+        assert_eq!(
+            fills,
+            vec![
+                Fill {
+                    id: 1,
+                    side: Side::BUY,
+                    maker_id: 1,
+                    price: Price::new(100),
+                    amount: Amount::new(5),
+                    taker_uuid: 4,
+                    maker_uuid: 1,
+                    maker_account: to_bech32(sender),
+                    taker_account: Some(to_bech32(taker)),
+                    fees: Some(MOCK_FEES),
+                    maker_fee: 0,
+                    taker_fee: 0,
+                    decimal_price: None,
+                    decimal_amount: None,
+                },
+                Fill {
+                    id: 2,
+                    side: Side::BUY,
+                    maker_id: 2,
+                    price: Price::new(100),
+                    amount: Amount::new(2),
+                    taker_uuid: 4,
+                    maker_uuid: 2,
+                    maker_account: to_bech32(sender),
+                    taker_account: Some(to_bech32(taker)),
+                    fees: Some(MOCK_FEES),
+                    maker_fee: 0,
+                    taker_fee: 0,
+                    decimal_price: None,
+                    decimal_amount: None,
+                },
+            ]
+        );
+        assert_eq!(head(&desk, SELL_HEAD_SLOT), 2);
+        assert_eq!(head(&desk, BUY_HEAD_SLOT), 0);
+        let id_key = Word::from([Felt::new(2), Felt::new(0), Felt::new(0), Felt::new(0)]);
+        let entry = desk.storage().get_map_item(SELL_BOOK_SLOT, id_key).unwrap();
+        assert_eq!(entry[0].as_int(), 3);
+
+        // Sweeps the sell side and rests the remainder on the buy side
+        offer(&chain, &mut desk, sender, (5, Side::BUY, 10, 120));
+        let fills = read_fills(&desk, 2, NetworkId::Testnet).unwrap();
+        assert_eq!(
+            fills
+                .iter()
+                .map(|fill| (fill.maker_id, fill.price.units(), fill.amount.units()))
+                .collect::<Vec<_>>(),
+            vec![(2, 100, 3), (3, 110, 5)]
+        );
+        assert_eq!(head(&desk, SELL_HEAD_SLOT), 0);
+        assert_eq!(head(&desk, BUY_HEAD_SLOT), 1);
+
+        // A sell below the best bid crosses it
+        offer(&chain, &mut desk, sender, (6, Side::SELL, 1, 90));
+        let fills = read_fills(&desk, 4, NetworkId::Testnet).unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].side, Side::SELL);
+        assert_eq!(fills[0].price, Price::new(120));
+        assert_eq!(fills[0].taker_uuid, 6);
+        assert_eq!(fills[0].maker_uuid, 5);
+    }
+
+    #[test]
+    fn test_read_book_aggregates_levels() {
+        let (chain, mut desk, sender) = mock_desk();
+        assert_eq!(
+            read_book(&desk, NetworkId::Testnet).unwrap(),
+            OrderBook::default()
+        );
+
+        offer(&chain, &mut desk, sender, (31, Side::SELL, 5, 110));
+        offer(&chain, &mut desk, sender, (32, Side::SELL, 3, 100));
+        offer(&chain, &mut desk, sender, (33, Side::SELL, 2, 110));
+        offer(&chain, &mut desk, sender, (34, Side::BUY, 4, 90));
+        offer(&chain, &mut desk, sender, (35, Side::BUY, 1, 95));
+
+        let book = read_book(&desk, NetworkId::Testnet).unwrap();
+        assert_eq!(
+            book.asks
+                .iter()
+                .map(|entry| (
+                    entry.id,
+                    entry.price.units(),
+                    entry.amount.units(),
+                    entry.uuid
+                ))
+                .collect::<Vec<_>>(),
+            vec![(2, 100, 3, 32), (1, 110, 5, 31), (3, 110, 2, 33)]
+        );
+        assert_eq!(
+            book.bids
+                .iter()
+                .map(|entry| (
+                    entry.id,
+                    entry.price.units(),
+                    entry.amount.units(),
+                    entry.uuid
+                ))
+                .collect::<Vec<_>>(),
+            vec![(2, 95, 1, 35), (1, 90, 4, 34)]
+        );
+        assert!(
+            book.asks
+                .iter()
+                .all(|entry| entry.account == to_bech32(sender))
+        );
+
+        assert_eq!(
+            book.ask_levels(),
+            vec![
+                PriceLevel {
+                    price: Price::new(100),
+                    amount: Amount::new(3),
+                    orders: 1,
+                },
+                PriceLevel {
+                    price: Price::new(110),
+                    amount: Amount::new(7),
+                    orders: 2,
+                },
+            ]
+        );
+        assert_eq!(book.bid_levels().len(), 2);
+        assert_eq!(book.bid_levels()[0].price, Price::new(95));
+
+        let scale = Scale {
+            base_decimals: 1,
+            quote_decimals: 2,
+        };
+        let book = book.with_decimals(scale);
+        assert_eq!(book.asks[0].decimal_price.as_deref(), Some("1"));
+        assert_eq!(book.asks[0].decimal_amount.as_deref(), Some("0.3"));
+        assert_eq!(book.bids[0].decimal_price.as_deref(), Some("0.95"));
+    }
+
+    #[test]
+    fn test_intents_are_indexed_and_unique() {
+        let (chain, mut desk, sender) = mock_desk();
+
+        offer(&chain, &mut desk, sender, (21, Side::SELL, 5, 100));
+        offer(&chain, &mut desk, sender, (22, Side::SELL, 5, 101));
+        assert_eq!(entry_id_by_uuid(&desk, Side::SELL, 21).unwrap(), Some(1));
+        assert_eq!(entry_id_by_uuid(&desk, Side::SELL, 22).unwrap(), Some(2));
+        assert_eq!(entry_id_by_uuid(&desk, Side::BUY, 21).unwrap(), None);
+
+        // Replaying an offer is rejected
+        assert!(try_offer(&chain, &mut desk, sender, (21, Side::SELL, 5, 100)).is_err());
+        assert_eq!(sell_ids(&desk), vec![1, 2]);
+
+        // A fully filled offer no longer rests, but its intent stays used
+        offer(&chain, &mut desk, sender, (23, Side::BUY, 5, 100));
+        assert_eq!(entry_id_by_uuid(&desk, Side::SELL, 21).unwrap(), None);
+        assert!(is_uuid_used(&desk, Side::SELL, 21).unwrap());
+        assert!(is_uuid_used(&desk, Side::BUY, 23).unwrap());
+        assert!(try_offer(&chain, &mut desk, sender, (23, Side::BUY, 5, 90)).is_err());
+
+        cancel(&chain, &mut desk, sender, (22, Side::SELL, None)).unwrap();
+        assert_eq!(entry_id_by_uuid(&desk, Side::SELL, 22).unwrap(), None);
+        assert!(try_offer(&chain, &mut desk, sender, (22, Side::SELL, 5, 101)).is_err());
+    }
+
+    #[test]
+    fn test_cancel_offer_unlinks_entry() {
+        let (chain, mut desk, sender) = mock_desk();
+
+        for (uuid, price) in [(11, 100), (12, 101), (13, 102), (14, 103)] {
+            offer(&chain, &mut desk, sender, (uuid, Side::SELL, 5, price));
+        }
+        assert_eq!(sell_ids(&desk), vec![1, 2, 3, 4]);
+        assert_eq!(entry_sender(&desk, Side::SELL, 2).unwrap(), Some(sender));
+
+        // Only the account that placed the offer can cancel it
+        let other = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+        for entry_id in [Some(2), None] {
+            let err = cancel(&chain, &mut desk, other, (12, Side::SELL, entry_id)).unwrap_err();
+            assert!(err.contains("Offer was placed by another account"), "{err}");
+        }
+        assert_eq!(sell_ids(&desk), vec![1, 2, 3, 4]);
+
+        // The intent must match the entry
+        assert!(cancel(&chain, &mut desk, sender, (99, Side::SELL, Some(2))).is_err());
+        assert!(cancel(&chain, &mut desk, sender, (99, Side::SELL, None)).is_err());
+        assert!(cancel(&chain, &mut desk, sender, (12, Side::BUY, None)).is_err());
+
+        cancel(&chain, &mut desk, sender, (12, Side::SELL, Some(2))).unwrap();
+        assert_eq!(sell_ids(&desk), vec![1, 3, 4]);
+        assert_eq!(entry_sender(&desk, Side::SELL, 2).unwrap(), None);
+
+        cancel(&chain, &mut desk, sender, (11, Side::SELL, None)).unwrap();
+        assert_eq!(sell_ids(&desk), vec![3, 4]);
+
+        cancel(&chain, &mut desk, sender, (14, Side::SELL, None)).unwrap();
+        assert_eq!(sell_ids(&desk), vec![3]);
+
+        // Cancelled entries are gone for good
+        assert!(cancel(&chain, &mut desk, sender, (14, Side::SELL, Some(4))).is_err());
+
+        cancel(&chain, &mut desk, sender, (13, Side::SELL, Some(3))).unwrap();
+        assert_eq!(sell_ids(&desk), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_time_in_force() {
+        let (mut chain, mut desk, sender) = mock_desk();
+        chain.prove_next_block().unwrap();
+        let block_num = chain.latest_block_header().block_num().as_u32();
+        let gtd = TimeInForce::GTD {
+            expiry_block: block_num + 2,
+        };
+
+        // An offer cannot be placed past its expiry
+        let expired = TimeInForce::GTD {
+            expiry_block: block_num,
+        };
+        assert!(place(&chain, &mut desk, sender, (40, Side::SELL, 5, 100), expired).is_err());
+
+        place(&chain, &mut desk, sender, (41, Side::SELL, 5, 100), gtd).unwrap();
+        offer(&chain, &mut desk, sender, (42, Side::SELL, 5, 110));
+        assert_eq!(
+            read_side(&desk, Side::SELL, NetworkId::Testnet).unwrap()[0].expiry_block,
+            Some(block_num + 2)
+        );
+
+        // An immediate or cancel remainder does not rest
+        place(
+            &chain,
+            &mut desk,
+            sender,
+            (43, Side::BUY, 7, 100),
+            TimeInForce::IOC,
+        )
+        .unwrap();
+        assert_eq!(read_fills(&desk, 0, NetworkId::Testnet).unwrap().len(), 1);
+        assert_eq!(head(&desk, BUY_HEAD_SLOT), 0);
+        assert_eq!(sell_ids(&desk), vec![2]);
+
+        place(&chain, &mut desk, sender, (44, Side::SELL, 3, 100), gtd).unwrap();
+        assert_eq!(sell_ids(&desk), vec![3, 2]);
+
+        // Once expired, the offer is skipped and pruned when matching
+        chain.prove_until_block(block_num + 2).unwrap();
+        let book = read_book(&desk, NetworkId::Testnet).unwrap();
+        assert_eq!(book.clone().live_at(block_num + 2).asks.len(), 1);
+        offer(&chain, &mut desk, sender, (45, Side::BUY, 1, 120));
+        let fills = read_fills(&desk, 1, NetworkId::Testnet).unwrap();
+        assert_eq!((fills[0].maker_uuid, fills[0].price.units()), (42, 110));
+        assert_eq!(sell_ids(&desk), vec![2]);
+    }
+
+    #[test]
+    fn test_desk_status_gates_offers() {
+        let (chain, mut desk, sender) = mock_desk();
+        assert_eq!(read_status(&desk).unwrap(), DeskStatus::Open);
+        offer(&chain, &mut desk, sender, (51, Side::SELL, 5, 100));
+        offer(&chain, &mut desk, sender, (52, Side::SELL, 5, 101));
+        offer(&chain, &mut desk, sender, (53, Side::SELL, 5, 102));
+
+        // Inserts are refused unless open, cancels are still accepted
+        for (status, uuid) in [
+            (DeskStatus::Paused, 51),
+            (DeskStatus::CancelOnly, 52),
+            (DeskStatus::Closed, 53),
+        ] {
+            update_status(&chain, &mut desk, sender, status.to_word()).unwrap();
+            assert_eq!(read_status(&desk).unwrap(), status);
+            assert!(!status.accepts_offers());
+            assert!(try_offer(&chain, &mut desk, sender, (60, Side::BUY, 1, 90)).is_err());
+            cancel(&chain, &mut desk, sender, (uuid, Side::SELL, None)).unwrap();
+        }
+        assert_eq!(sell_ids(&desk), Vec::<u64>::new());
+
+        update_status(&chain, &mut desk, sender, DeskStatus::Open.to_word()).unwrap();
+        offer(&chain, &mut desk, sender, (60, Side::BUY, 1, 90));
+
+        // Only the known status codes can be stored
+        for word in [[4, 0, 0, 0], [1, 1, 1, 1]] {
+            assert!(update_status(&chain, &mut desk, sender, word).is_err());
+        }
+        assert_eq!(read_status(&desk).unwrap(), DeskStatus::Open);
+        assert!(DeskStatus::from_code(4).is_err());
+
+        // Only the owner can update the status
+        assert_eq!(read_owner(&desk).unwrap(), sender);
+        let other = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+        let closed = DeskStatus::Closed.to_word();
+        assert!(update_status(&chain, &mut desk, other, closed).is_err());
+        assert_eq!(read_status(&desk).unwrap(), DeskStatus::Open);
+    }
+
+    #[test]
+    fn test_fees_charged_on_fills() {
+        let (chain, mut desk, sender) = mock_desk();
+        assert_eq!(read_fees(&desk).unwrap(), MOCK_FEES);
+
+        offer(&chain, &mut desk, sender, (71, Side::SELL, 300, 1_000));
+        offer(&chain, &mut desk, sender, (72, Side::BUY, 300, 1_000));
+        let mut fill = read_fills(&desk, 0, NetworkId::Testnet).unwrap().remove(0);
+        assert_eq!((fill.maker_fee, fill.taker_fee), (0, 0));
+        assert_eq!(fill.fees, Some(MOCK_FEES));
+
+        // 10 and 25 bps of a 300_000 notional, then of 3 with rounding up,
+        // with the schedule recorded at fill time whatever the current one
+        fill.charge_fees(FeeSchedule::default(), Scale::default())
+            .unwrap();
+        assert_eq!((fill.maker_fee, fill.taker_fee), (300, 750));
+        let scale = Scale {
+            base_decimals: 5,
+            quote_decimals: 0,
+        };
+        fill.charge_fees(MOCK_FEES, scale).unwrap();
+        assert_eq!((fill.maker_fee, fill.taker_fee), (1, 1));
+
+        let fill = fill.with_decimals(scale);
+        assert_eq!(fill.decimal_price.as_deref(), Some("1000"));
+        assert_eq!(fill.decimal_amount.as_deref(), Some("0.003"));
+
+        let excessive = FeeSchedule {
+            maker_bps: 0,
+            taker_bps: 10_001,
+        };
+        assert!(excessive.check().is_err());
+        MOCK_FEES.check().unwrap();
+    }
+
+    #[test]
+    fn test_order_limits_refuse_offers() {
+        let limits = OrderLimits {
+            tick_size: Price::new(5),
+            lot_size: Amount::new(10),
+            min_notional: 1_000,
+            max_notional: 100_000,
+        };
+        let (chain, mut desk, sender) = mock_desk_with_limits(limits);
+        assert_eq!(read_limits(&desk, Scale::default()).unwrap(), limits);
+
+        // Off tick, off lot, below the minimum, above the maximum and over 64 bits
+        for (uuid, amount, price, error) in [
+            (81, 100, 101, "tick size"),
+            (82, 105, 100, "lot size"),
+            (83, 10, 50, "below the desk minimum"),
+            (84, 10_000, 100, "above the desk maximum"),
+            (85, 10 << 37, 5 << 28, "above the desk maximum"),
+        ] {
+            let checked = limits.check(amount.into(), price.into(), Scale::default());
+            assert!(checked.unwrap_err().to_string().contains(error));
+            let consumed = try_offer(&chain, &mut desk, sender, (uuid, Side::SELL, amount, price));
+            assert!(consumed.unwrap_err().contains(error));
+        }
+        assert_eq!(sell_ids(&desk), Vec::<u64>::new());
+
+        for (uuid, amount, price) in [(86, 100, 100), (87, 10, 100), (88, 1_000, 100)] {
+            limits
+                .check(amount.into(), price.into(), Scale::default())
+                .unwrap();
+            offer(&chain, &mut desk, sender, (uuid, Side::SELL, amount, price));
+        }
+        assert_eq!(sell_ids(&desk).len(), 3);
+
+        // Notional bounds are stored per base unit
+        let scale = Scale {
+            base_decimals: 8,
+            quote_decimals: 6,
+        };
+        let word = limits.storage_word(scale).unwrap();
+        assert_eq!(word[2].as_int(), 100_000_000_000);
+        assert!(
+            OrderLimits {
+                min_notional: 200_000,
+                ..limits
+            }
+            .storage_word(scale)
+            .is_err()
+        );
+    }
+}
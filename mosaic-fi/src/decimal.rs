@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Decimals of the base and quote faucets of a market
+///
+/// Amounts are counted in base units at the base decimals. Prices are quote
+/// units, at the quote decimals, for one whole base token.
+#[derive(
+    PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema, Debug, Clone, Copy, Default,
+)]
+pub struct Scale {
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+}
+
+/// Quantity of the base asset, in base units
+#[derive(
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    schemars::JsonSchema,
+    Debug,
+    Clone,
+    Copy,
+    Default,
+)]
+#[serde(transparent)]
+pub struct Amount(u64);
+
+/// Quote units paid for one whole base token
+#[derive(
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    schemars::JsonSchema,
+    Debug,
+    Clone,
+    Copy,
+    Default,
+)]
+#[serde(transparent)]
+pub struct Price(u64);
+
+impl Amount {
+    pub const fn new(units: u64) -> Self {
+        Amount(units)
+    }
+
+    /// Integer encoding stored in notes and in the desk book
+    pub const fn units(self) -> u64 {
+        self.0
+    }
+
+    /// Parse a human amount such as "0.5" for a base faucet
+    pub fn parse(value: &str, scale: Scale) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Amount(parse_fixed(value, scale.base_decimals)?))
+    }
+
+    pub fn to_decimal(self, scale: Scale) -> String {
+        format_fixed(self.0, scale.base_decimals)
+    }
+
+    pub fn saturating_add(self, other: Amount) -> Amount {
+        Amount(self.0.saturating_add(other.0))
+    }
+}
+
+impl Price {
+    pub const fn new(units: u64) -> Self {
+        Price(units)
+    }
+
+    /// Integer encoding stored in notes and compared by the desk book
+    pub const fn units(self) -> u64 {
+        self.0
+    }
+
+    /// Parse a human price such as "65000.25" quote tokens per base token
+    pub fn parse(value: &str, scale: Scale) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Price(parse_fixed(value, scale.quote_decimals)?))
+    }
+
+    pub fn to_decimal(self, scale: Scale) -> String {
+        format_fixed(self.0, scale.quote_decimals)
+    }
+
+    /// Quote units owed for an amount at this price
+    ///
+    /// Fractions of a quote unit are rounded up, in favour of the receiver.
+    pub fn notional(self, amount: Amount, scale: Scale) -> Result<u64, Box<dyn std::error::Error>> {
+        let one = 10u128
+            .checked_pow(scale.base_decimals as u32)
+            .ok_or("Too many base decimals")?;
+        let notional = (amount.0 as u128 * self.0 as u128).div_ceil(one);
+        Ok(u64::try_from(notional).map_err(|_| "Notional overflows")?)
+    }
+}
+
+impl From<u64> for Amount {
+    fn from(units: u64) -> Self {
+        Amount(units)
+    }
+}
+
+impl From<Amount> for u64 {
+    fn from(amount: Amount) -> Self {
+        amount.0
+    }
+}
+
+impl From<u64> for Price {
+    fn from(units: u64) -> Self {
+        Price(units)
+    }
+}
+
+impl From<Price> for u64 {
+    fn from(price: Price) -> Self {
+        price.0
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+fn parse_fixed(value: &str, decimals: u8) -> Result<u64, Box<dyn std::error::Error>> {
+    let value = value.trim();
+    let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if integer.is_empty() && fraction.is_empty() {
+        return Err(format!("Invalid decimal '{}'", value).into());
+    }
+    if !integer
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return Err(format!("Invalid decimal '{}'", value).into());
+    }
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(format!("'{}' has more than {} decimals", value, decimals).into());
+    }
+
+    let digits = format!(
+        "{}{:0<width$}",
+        integer,
+        fraction,
+        width = decimals as usize
+    );
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    digits
+        .parse::<u64>()
+        .map_err(|_| format!("'{}' overflows", value).into())
+}
+
+fn format_fixed(units: u64, decimals: u8) -> String {
+    let one = 10u128.pow(decimals as u32);
+    let integer = units as u128 / one;
+    let fraction = units as u128 % one;
+    if fraction == 0 {
+        return integer.to_string();
+    }
+    let fraction = format!("{:0>width$}", fraction, width = decimals as usize);
+    format!("{}.{}", integer, fraction.trim_end_matches('0'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BTC_USDC: Scale = Scale {
+        base_decimals: 8,
+        quote_decimals: 6,
+    };
+
+    #[test]
+    fn test_parse_and_format() {
+        let price = Price::parse("65000.25", BTC_USDC).unwrap();
+        assert_eq!(price.units(), 65_000_250_000);
+        assert_eq!(price.to_decimal(BTC_USDC), "65000.25");
+
+        let amount = Amount::parse("0.5", BTC_USDC).unwrap();
+        assert_eq!(amount.units(), 50_000_000);
+        assert_eq!(amount.to_decimal(BTC_USDC), "0.5");
+        assert_eq!(Amount::parse("2", BTC_USDC).unwrap().units(), 200_000_000);
+        assert_eq!(Amount::parse(".00000001", BTC_USDC).unwrap().units(), 1);
+
+        assert!(Amount::parse("0.000000001", BTC_USDC).is_err());
+        assert!(Amount::parse("-1", BTC_USDC).is_err());
+        assert!(Amount::parse("1e5", BTC_USDC).is_err());
+        assert!(Amount::parse(".", BTC_USDC).is_err());
+        assert!(Price::parse("18446744073709551616", Scale::default()).is_err());
+    }
+
+    #[test]
+    fn test_notional() {
+        let price = Price::parse("65000.25", BTC_USDC).unwrap();
+        let amount = Amount::parse("0.5", BTC_USDC).unwrap();
+        assert_eq!(price.notional(amount, BTC_USDC).unwrap(), 32_500_125_000);
+
+        // A satoshi at that price is 650.0025 micro USDC
+        assert_eq!(price.notional(Amount::new(1), BTC_USDC).unwrap(), 651);
+
+        // Without decimals the price is quote units per base unit
+        assert_eq!(
+            Price::new(100)
+                .notional(Amount::new(6), Scale::default())
+                .unwrap(),
+            600
+        );
+        assert!(
+            Price::new(u64::MAX)
+                .notional(Amount::new(2), Scale::default())
+                .is_err()
+        );
+    }
+}
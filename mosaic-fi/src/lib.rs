@@ -1,6 +1,13 @@
 pub mod account;
+pub mod book;
+pub mod decimal;
 pub mod note;
 
+#[cfg(test)]
+mod testing;
+
+use miden_objects::account::AccountId;
+use miden_objects::address::Address;
 use serde::{Deserialize, Serialize};
 
 pub use account::{AccountOrder, AccountOrderResult};
@@ -30,3 +37,33 @@ pub struct Market {
     /// Quote currency (e.g., USDC in BTC/USDC)
     pub quote: Currency,
 }
+
+impl Market {
+    /// Faucet account ids of the base and quote currencies
+    pub fn faucet_ids(&self) -> Result<(AccountId, AccountId), Box<dyn std::error::Error>> {
+        Ok((
+            parse_issuer(&self.base.issuer)?,
+            parse_issuer(&self.quote.issuer)?,
+        ))
+    }
+
+    /// Canonical market id, derived from the base and quote faucet ids
+    pub fn id(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let (base, quote) = self.faucet_ids()?;
+        Ok(format!("{}/{}", base.to_hex(), quote.to_hex()))
+    }
+}
+
+impl std::fmt::Display for Market {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.base.code, self.quote.code)
+    }
+}
+
+fn parse_issuer(issuer: &str) -> Result<AccountId, Box<dyn std::error::Error>> {
+    match Address::from_bech32(issuer) {
+        Ok((_network_id, Address::AccountId(address))) => Ok(address.id()),
+        Ok(_) => Err(format!("Issuer {} is not an account", issuer).into()),
+        Err(e) => Err(format!("Invalid issuer {}: {}", issuer, e).into()),
+    }
+}
@@ -1,5 +1,9 @@
+use crate::book::DeskStatus;
 use miden_objects::Word;
-use miden_objects::account::AccountId;
+use miden_objects::account::{AccountId, NetworkId};
+use miden_objects::asset::{Asset, FungibleAsset};
+use miden_objects::note::{NoteExecutionHint, NoteTag};
+use miden_objects::{Felt, crypto::hash::rpo::Rpo256, crypto::rand::RpoRandomCoin};
 use mosaic_miden::{
     MidenTransactionId,
     note::{MidenAbstractNote, MidenNote, NoteType, Value},
@@ -12,16 +16,128 @@ pub enum Side {
     SELL,
 }
 
-pub type Market = String;
+/// How long an offer rests in the desk book
+#[derive(PartialEq, Serialize, Deserialize, schemars::JsonSchema, Debug, Clone, Copy, Default)]
+pub enum TimeInForce {
+    /// Good till cancelled
+    #[default]
+    GTC,
+    /// Good till date, the offer leaves the book at the expiry block
+    GTD { expiry_block: u32 },
+    /// Immediate or cancel, the unfilled remainder never rests
+    IOC,
+}
+
+impl TimeInForce {
+    /// Block at which the offer expires, zero when it never does
+    pub fn expiry_block(&self) -> u32 {
+        match self {
+            TimeInForce::GTD { expiry_block } => *expiry_block,
+            TimeInForce::GTC | TimeInForce::IOC => 0,
+        }
+    }
+
+    pub fn is_immediate(&self) -> bool {
+        matches!(self, TimeInForce::IOC)
+    }
+}
+
+pub use crate::Market;
+pub use crate::decimal::{Amount, Price, Scale};
+
 pub type UUID = u128;
-pub type Amount = u64;
-pub type Price = u64;
 
-#[derive(PartialEq, Serialize, Deserialize, schemars::JsonSchema, Debug, Clone)]
+/// Account or tag an order note is addressed to
+#[derive(PartialEq, Serialize, schemars::JsonSchema, Debug, Clone)]
 pub enum Recipient {
+    /// Desk account consuming the note, in bech32 format
+    Desk(String),
+    /// Client account consuming the note, in bech32 format
+    Client(String),
+    /// Any account listening to the note tag
+    Broadcast(u32),
+}
+
+/// Recipient as stored, including the AccountId recipients written before
+/// recipients told desks and clients apart
+#[derive(Deserialize)]
+enum StoredRecipient {
+    Desk(String),
+    Client(String),
+    Broadcast(u32),
     AccountId(String),
 }
 
+impl From<StoredRecipient> for Recipient {
+    fn from(stored: StoredRecipient) -> Self {
+        match stored {
+            StoredRecipient::Desk(account) => Recipient::Desk(account),
+            StoredRecipient::Client(account) => Recipient::Client(account),
+            StoredRecipient::Broadcast(tag) => Recipient::Broadcast(tag),
+            // Liquidity offers were addressed to a "desk:<market>" placeholder
+            // and sent with the default tag
+            StoredRecipient::AccountId(placeholder) if placeholder.starts_with("desk:") => {
+                Recipient::broadcast()
+            }
+            // Account funding was the only other note with a recipient
+            StoredRecipient::AccountId(account) => Recipient::Client(account),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Recipient {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        StoredRecipient::deserialize(deserializer).map(Recipient::from)
+    }
+}
+
+impl Recipient {
+    pub fn desk(account: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        parse_address(account)?;
+        Ok(Recipient::Desk(account.to_string()))
+    }
+
+    pub fn client(account: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        parse_address(account)?;
+        Ok(Recipient::Client(account.to_string()))
+    }
+
+    /// Addressed to whoever consumes notes with the default Mosaic tag
+    pub fn broadcast() -> Self {
+        Recipient::Broadcast(mosaic_miden::note::broadcast_tag().as_u32())
+    }
+
+    /// Account the note is addressed to, with the network of its address
+    pub fn account(&self) -> Result<Option<(NetworkId, AccountId)>, Box<dyn std::error::Error>> {
+        match self {
+            Recipient::Desk(account) | Recipient::Client(account) => {
+                Ok(Some(parse_address(account)?))
+            }
+            Recipient::Broadcast(_) => Ok(None),
+        }
+    }
+
+    /// Tag the note is sent with
+    pub fn note_tag(&self) -> Result<NoteTag, Box<dyn std::error::Error>> {
+        match self {
+            Recipient::Desk(account) | Recipient::Client(account) => {
+                Ok(NoteTag::from_account_id(parse_address(account)?.1))
+            }
+            Recipient::Broadcast(tag) => Ok(NoteTag::from(*tag)),
+        }
+    }
+
+    /// Reject an account address encoded for another network
+    pub fn check_network(&self, network_id: NetworkId) -> Result<(), Box<dyn std::error::Error>> {
+        match self.account()? {
+            Some((address_network, _)) if address_network != network_id => {
+                Err(format!("Recipient {:?} is not on network {}", self, network_id).into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 #[derive(PartialEq, Serialize, Deserialize, schemars::JsonSchema, Debug, Clone)]
 pub enum Order {
     // Notes emited by Desk, consumed by Client
@@ -34,29 +150,69 @@ pub enum Order {
         side: Side,
         amount: Amount,
         price: Price,
+        target_account_id: String, // bech32 format
     },
     QuoteRequestNoOffer {
         market: Market,
         uuid: UUID,
+        target_account_id: String, // bech32 format
+    },
+    // At that stage the order is firm
+    LimitBuyOrderLocked {
+        market: Market,
+        uuid: UUID,
+        target_account_id: String, // bech32 format
+    },
+    LimitBuyOrderNotLocked {
+        market: Market,
+        uuid: UUID,
+        target_account_id: String, // bech32 format
+    },
+    LimitSellOrderLocked {
+        market: Market,
+        uuid: UUID,
+        target_account_id: String, // bech32 format
+    },
+    LimitSellOrderNotLocked {
+        market: Market,
+        uuid: UUID,
+        target_account_id: String, // bech32 format
+    },
+    Settle {
+        // The client receives the traded asset by paying the desk in the same transaction
+        market: Market,
+        uuid: UUID,
+        side: Side, // Side of the client
+        amount: Amount,
+        price: Price,
+        target_account_id: String, // bech32 format
+        // Taker fee of the desk in quote units, paid by the client
+        #[serde(default)]
+        fee: u64,
+        // Tells apart the payback notes of settlements of the same trade
+        #[serde(default)]
+        nonce: u64,
     },
-    LimitBuyOrderLocked,
-    LimitBuyOrderNotLocked, // At that stage the order is firm
-    LimitSellOrderLocked,
-    LimitSellOrderNotLocked,
 
     // Notes emitted by Client, consumed by Desk
+    // Amounts and prices may be left out when given as decimals instead
     QuoteRequest {
         market: Market,
         uuid: UUID,
         side: Side,
+        #[serde(default)]
         amount: Amount,
     },
     LimitOrder {
         market: Market,
         uuid: UUID,
         side: Side,
+        #[serde(default)]
         amount: Amount,
+        #[serde(default)]
         price: Price,
+        #[serde(default)]
+        time_in_force: TimeInForce,
     },
 
     // Notes emetted by Liqudity Providers, consumed by Desk
@@ -64,8 +220,18 @@ pub enum Order {
         market: Market,
         uuid: UUID,
         side: Side,
+        #[serde(default)]
         amount: Amount,
+        #[serde(default)]
         price: Price,
+        #[serde(default)]
+        time_in_force: TimeInForce,
+    },
+    CancelOffer {
+        market: Market,
+        uuid: UUID, // Intent of the offer to cancel
+        side: Side,
+        entry_id: Option<u64>, // Book entry id, looked up by intent when missing
     },
 
     // Notes emitted by Faucet, consumed by Client (P2ID note)
@@ -73,6 +239,78 @@ pub enum Order {
         target_account_id: String, // bech32 format
         amount: Amount,
     },
+
+    // Notes emitted by a wallet, consumed by another account (P2ID or reclaimable P2IDE note)
+    Transfer {
+        target_account_id: String, // bech32 format
+        asset: String,             // faucet, bech32 format
+        amount: Amount,
+        /// Blocks after which the sender can reclaim the note
+        #[serde(default)]
+        reclaim_after: Option<u32>,
+    },
+}
+
+impl Order {
+    pub fn market(&self) -> Option<&Market> {
+        match self {
+            Order::KYCPassed { market }
+            | Order::QuoteRequestOffer { market, .. }
+            | Order::QuoteRequestNoOffer { market, .. }
+            | Order::LimitBuyOrderLocked { market, .. }
+            | Order::LimitBuyOrderNotLocked { market, .. }
+            | Order::LimitSellOrderLocked { market, .. }
+            | Order::LimitSellOrderNotLocked { market, .. }
+            | Order::Settle { market, .. }
+            | Order::QuoteRequest { market, .. }
+            | Order::LimitOrder { market, .. }
+            | Order::LiquidityOffer { market, .. }
+            | Order::CancelOffer { market, .. } => Some(market),
+            Order::FundAccount { .. } | Order::Transfer { .. } => None,
+        }
+    }
+
+    /// Replace the amount and price of an order, when it has them
+    pub fn with_quantities(
+        mut self,
+        new_amount: Option<Amount>,
+        new_price: Option<Price>,
+    ) -> Result<Order, Box<dyn std::error::Error>> {
+        let (amount, price) = match &mut self {
+            Order::QuoteRequestOffer { amount, price, .. }
+            | Order::Settle { amount, price, .. }
+            | Order::LimitOrder { amount, price, .. }
+            | Order::LiquidityOffer { amount, price, .. } => (Some(amount), Some(price)),
+            Order::QuoteRequest { amount, .. }
+            | Order::FundAccount { amount, .. }
+            | Order::Transfer { amount, .. } => (Some(amount), None),
+            _ => (None, None),
+        };
+        if let Some(new_amount) = new_amount {
+            *amount.ok_or("Order has no amount")? = new_amount;
+        }
+        if let Some(new_price) = new_price {
+            *price.ok_or("Order has no price")? = new_price;
+        }
+        Ok(self)
+    }
+
+    /// Reject the orders sent to a desk whose amount or price was left out
+    pub fn check_quantities(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let (amount, price) = match self {
+            Order::QuoteRequest { amount, .. } => (*amount, None),
+            Order::LimitOrder { amount, price, .. }
+            | Order::LiquidityOffer { amount, price, .. } => (*amount, Some(*price)),
+            _ => return Ok(()),
+        };
+        if amount == Amount::default() {
+            return Err("Order amount is missing".into());
+        }
+        if price == Some(Price::default()) {
+            return Err("Order price is missing".into());
+        }
+        Ok(())
+    }
 }
 
 #[derive(PartialEq, Serialize, Deserialize, schemars::JsonSchema, Debug, Clone)]
@@ -90,47 +328,645 @@ pub struct MosaicNote {
     pub miden_note: MidenNote,
 }
 
+impl MosaicNote {
+    /// Check the note can be handed to a desk
+    ///
+    /// The note must be addressed to the desk or broadcast, and be tagged
+    /// accordingly.
+    pub fn check_desk_recipient(
+        &self,
+        desk_id: AccountId,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match &self.recipient {
+            Recipient::Desk(_) => {
+                let (_, account_id) = self.recipient.account()?.ok_or("Missing desk account")?;
+                if account_id != desk_id {
+                    return Err(format!(
+                        "Note is addressed to desk {}, not {}",
+                        account_id, desk_id
+                    )
+                    .into());
+                }
+            }
+            Recipient::Broadcast(_) => {}
+            Recipient::Client(account) => {
+                return Err(format!("Note is addressed to client {}", account).into());
+            }
+        }
+
+        let note = mosaic_miden::note::decode_note(&self.miden_note)?;
+        if note.metadata().tag() != self.recipient.note_tag()? {
+            return Err("Note tag does not match its recipient".into());
+        }
+        Ok(())
+    }
+}
+
+fn uuid_to_value(uuid: UUID) -> Value {
+    let uuid_high = (uuid >> 64) as u64;
+    let uuid_low = uuid as u64;
+    Value::Word([uuid_high, uuid_low, 0, 0])
+}
+
+fn side_to_value(side: Side) -> Value {
+    match side {
+        Side::SELL => Value::Element(0),
+        Side::BUY => Value::Element(1),
+    }
+}
+
+fn parse_account_id(account_id: &str) -> Result<AccountId, Box<dyn std::error::Error>> {
+    Ok(parse_address(account_id)?.1)
+}
+
+fn parse_address(account_id: &str) -> Result<(NetworkId, AccountId), Box<dyn std::error::Error>> {
+    let (network_id, address) = miden_objects::address::Address::from_bech32(account_id)?;
+    match address {
+        miden_objects::address::Address::AccountId(account_id_addr) => {
+            Ok((network_id, account_id_addr.id()))
+        }
+        _ => Err(format!("Invalid address type for account ID: {}", account_id).into()),
+    }
+}
+
+fn word_to_value(word: Word) -> Value {
+    Value::Word([
+        word[0].as_int(),
+        word[1].as_int(),
+        word[2].as_int(),
+        word[3].as_int(),
+    ])
+}
+
+fn random_word() -> Word {
+    use rand::Rng;
+    let mut thread_rng = rand::rng();
+    Word::from([
+        thread_rng.random::<u32>(),
+        thread_rng.random::<u32>(),
+        thread_rng.random::<u32>(),
+        thread_rng.random::<u32>(),
+    ])
+}
+
+/// Serial number of the payback note of a settlement, derived from the trade
+/// and the nonce of the settlement
+fn payback_serial(uuid: UUID, amount: Amount, price: Price, nonce: u64) -> Word {
+    Rpo256::hash_elements(&[
+        Felt::new((uuid >> 64) as u64),
+        Felt::new(uuid as u64),
+        Felt::new(amount.units()),
+        Felt::new(price.units()),
+        Felt::new(nonce),
+    ])
+}
+
+/// Rebuild the payback note a client sends to the desk when consuming a settlement
+///
+/// The desk consumes it to collect what the client paid.
+pub fn compile_settlement_payback(
+    desk_account_id: AccountId,
+    order: &Order,
+    scale: Scale,
+) -> Result<MidenNote, Box<dyn std::error::Error>> {
+    let Order::Settle {
+        market,
+        uuid,
+        side,
+        amount,
+        price,
+        target_account_id,
+        fee,
+        nonce,
+    } = order
+    else {
+        return Err("Only settlements have a payback note".into());
+    };
+
+    let client_account = parse_account_id(target_account_id)?;
+    let (_, paid) = settlement_assets(market, scale, *side, *amount, *price, *fee)?;
+    mosaic_miden::note::compile_payback_note(
+        client_account,
+        desk_account_id,
+        paid,
+        payback_serial(*uuid, *amount, *price, *nonce),
+    )
+}
+
+/// Serial number of the refund note of a backed offer, derived as in `book.masm`
+fn refund_serial(desk_account_id: AccountId, uuid: UUID, side: Side) -> Word {
+    let intent = Word::from([
+        Felt::new((uuid >> 64) as u64),
+        Felt::new(uuid as u64),
+        Felt::new(0),
+        Felt::new(0),
+    ]);
+    let side = match side {
+        Side::SELL => Felt::new(0),
+        Side::BUY => Felt::new(1),
+    };
+    let offer = Word::from([
+        desk_account_id.suffix(),
+        desk_account_id.prefix().as_felt(),
+        Felt::new(0),
+        side,
+    ]);
+    Rpo256::merge(&[intent, offer])
+}
+
+/// Rebuild the note refunding the unfilled amount of a backed offer
+///
+/// The desk sends it to the account that placed the offer when the offer is
+/// cancelled, expires or leaves an immediate or cancel remainder. That account
+/// consumes it to collect the asset it offered for the remaining amount.
+pub fn compile_offer_refund(
+    desk_account_id: AccountId,
+    account_id: AccountId,
+    order: &Order,
+    remaining: Amount,
+    scale: Scale,
+) -> Result<MidenNote, Box<dyn std::error::Error>> {
+    let (Order::LiquidityOffer {
+        market,
+        uuid,
+        side,
+        price,
+        ..
+    }
+    | Order::LimitOrder {
+        market,
+        uuid,
+        side,
+        price,
+        ..
+    }) = order
+    else {
+        return Err("Only liquidity offers and limit orders are refunded".into());
+    };
+
+    let asset = offered_asset(market, scale, *side, remaining, *price)?;
+    mosaic_miden::note::compile_payback_note(
+        desk_account_id,
+        account_id,
+        asset,
+        refund_serial(desk_account_id, *uuid, *side),
+    )
+}
+
+fn account_id_to_value(account_id: AccountId) -> Value {
+    Value::Word([
+        account_id.suffix().as_int(),
+        account_id.prefix().as_u64(),
+        0,
+        0,
+    ])
+}
+
+/// Asset offered by an order, taken from the base faucet when selling and
+/// from the quote faucet when buying
+pub fn offered_asset(
+    market: &Market,
+    scale: Scale,
+    side: Side,
+    amount: Amount,
+    price: Price,
+) -> Result<Asset, Box<dyn std::error::Error>> {
+    let (issuer, quantity) = match side {
+        Side::SELL => (&market.base.issuer, amount.units()),
+        Side::BUY => (&market.quote.issuer, price.notional(amount, scale)?),
+    };
+    let faucet_id = parse_account_id(issuer)?;
+    Ok(FungibleAsset::new(faucet_id, quantity)?.into())
+}
+
+/// Assets of a settlement, delivered by the desk and paid by the client
+///
+/// The fee is taken in the quote asset, on top of what a buying client pays
+/// or out of what a selling client receives.
+fn settlement_assets(
+    market: &Market,
+    scale: Scale,
+    side: Side,
+    amount: Amount,
+    price: Price,
+    fee: u64,
+) -> Result<(Asset, Asset), Box<dyn std::error::Error>> {
+    let base = offered_asset(market, scale, Side::SELL, amount, price)?;
+    let quote_faucet_id = parse_account_id(&market.quote.issuer)?;
+    let notional = price.notional(amount, scale)?;
+
+    match side {
+        Side::BUY => {
+            let paid = notional
+                .checked_add(fee)
+                .ok_or("Settlement fee overflows")?;
+            Ok((base, FungibleAsset::new(quote_faucet_id, paid)?.into()))
+        }
+        Side::SELL => {
+            let received = notional
+                .checked_sub(fee)
+                .ok_or("Settlement fee exceeds the notional")?;
+            Ok((FungibleAsset::new(quote_faucet_id, received)?.into(), base))
+        }
+    }
+}
+
+/// Compile a private note consumed by a desk, linked against the book library
+fn compile_desk_note(
+    account_id: AccountId,
+    recipient: &Recipient,
+    program: &str,
+    inputs: Vec<(String, Value)>,
+    assets: Vec<Asset>,
+) -> Result<MidenNote, Box<dyn std::error::Error>> {
+    let abs_note = MidenAbstractNote {
+        version: mosaic_miden::version::VERSION_STRING.to_string(),
+        note_type: NoteType::Private,
+        program: program.to_string(),
+        libraries: vec![(
+            "external_contract::book".to_string(),
+            include_str!("../masm/accounts/book.masm").to_string(),
+        )],
+    };
+    let secret = Word::default();
+    mosaic_miden::note::compile_note_with_assets(
+        abs_note,
+        account_id,
+        secret,
+        inputs,
+        assets,
+        recipient.note_tag()?,
+    )
+}
+
+/// Compile a private note emitted by a desk and consumed by a client
+fn compile_client_note(
+    account_id: AccountId,
+    recipient: &Recipient,
+    program: &str,
+    inputs: Vec<(String, Value)>,
+    assets: Vec<Asset>,
+) -> Result<MidenNote, Box<dyn std::error::Error>> {
+    let abs_note = MidenAbstractNote {
+        version: mosaic_miden::version::VERSION_STRING.to_string(),
+        note_type: NoteType::Private,
+        program: program.to_string(),
+        libraries: vec![],
+    };
+    let secret = Word::default();
+    mosaic_miden::note::compile_note_with_assets(
+        abs_note,
+        account_id,
+        secret,
+        inputs,
+        assets,
+        recipient.note_tag()?,
+    )
+}
+
+/// Compile an order note, broadcast when the order is consumed by a desk
 pub fn compile_note_from_account_id(
     account_id: AccountId,
     order: Order,
+) -> Result<MosaicNote, Box<dyn std::error::Error>> {
+    compile_order_note(account_id, order, None, None)
+}
+
+/// Compile an order note addressed to a desk account, in bech32 format
+pub fn compile_desk_note_from_account_id(
+    account_id: AccountId,
+    order: Order,
+    desk_account: &str,
+) -> Result<MosaicNote, Box<dyn std::error::Error>> {
+    compile_order_note(account_id, order, Some(desk_account), None)
+}
+
+/// Compile an order note carrying assets from the faucets of its market
+///
+/// Only liquidity offers, limit orders and settlements can be backed. Offers
+/// and orders are addressed to the desk when one is given.
+pub fn compile_backed_note_from_account_id(
+    account_id: AccountId,
+    order: Order,
+    desk_account: Option<&str>,
+    scale: Scale,
 ) -> Result<MosaicNote, Box<dyn std::error::Error>> {
     match order {
-        Order::LiquidityOffer {
-            ref market,
+        Order::LiquidityOffer { .. } | Order::LimitOrder { .. } | Order::Settle { .. } => {
+            compile_order_note(account_id, order, desk_account, Some(scale))
+        }
+        _ => Err("Only liquidity offers, limit orders and settlements can carry assets".into()),
+    }
+}
+
+/// Compile a note from the desk owner setting the trading status of the desk
+pub fn compile_desk_status_note(
+    owner_id: AccountId,
+    desk_account: &str,
+    intent: UUID,
+    status: DeskStatus,
+) -> Result<MidenNote, Box<dyn std::error::Error>> {
+    let recipient = Recipient::desk(desk_account)?;
+    let inputs = vec![
+        ("intent".to_string(), uuid_to_value(intent)),
+        ("status".to_string(), Value::Word(status.to_word())),
+    ];
+    compile_desk_note(
+        owner_id,
+        &recipient,
+        include_str!("../masm/notes/desk_update_status.masm"),
+        inputs,
+        vec![],
+    )
+}
+
+fn desk_recipient(desk_account: Option<&str>) -> Result<Recipient, Box<dyn std::error::Error>> {
+    match desk_account {
+        Some(desk_account) => Recipient::desk(desk_account),
+        None => Ok(Recipient::broadcast()),
+    }
+}
+
+fn compile_order_note(
+    account_id: AccountId,
+    order: Order,
+    desk: Option<&str>,
+    backing: Option<Scale>,
+) -> Result<MosaicNote, Box<dyn std::error::Error>> {
+    match order {
+        Order::QuoteRequestOffer {
             uuid,
             side,
             amount,
             price,
+            ref target_account_id,
+            ..
+        } => {
+            let target_account = parse_account_id(target_account_id)?;
+            let inputs = vec![
+                ("uuid".to_string(), uuid_to_value(uuid)),
+                ("amount".to_string(), Value::Element(amount.units())),
+                ("price".to_string(), Value::Element(price.units())),
+                ("side".to_string(), side_to_value(side)),
+                ("offer".to_string(), Value::Element(1)),
+                ("target".to_string(), account_id_to_value(target_account)),
+            ];
+            let recipient = Recipient::client(target_account_id)?;
+            let miden_note = compile_client_note(
+                account_id,
+                &recipient,
+                include_str!("../masm/notes/desk_quote_response.masm"),
+                inputs,
+                vec![],
+            )?;
+
+            Ok(MosaicNote {
+                status: MosaicNoteStatus::New,
+                recipient,
+                order,
+                miden_note,
+            })
+        }
+        Order::QuoteRequestNoOffer {
+            uuid,
+            ref target_account_id,
+            ..
         } => {
-            let abs_note = MidenAbstractNote {
-                version: mosaic_miden::version::VERSION_STRING.to_string(),
-                note_type: NoteType::Private,
-                program: include_str!("../masm/notes/lp_liquidity_offer.masm").to_string(),
-                libraries: vec![(
-                    "external_contract::book".to_string(),
-                    include_str!("../masm/accounts/book.masm").to_string(),
-                )],
+            let target_account = parse_account_id(target_account_id)?;
+            let inputs = vec![
+                ("uuid".to_string(), uuid_to_value(uuid)),
+                ("amount".to_string(), Value::Element(0)),
+                ("price".to_string(), Value::Element(0)),
+                ("side".to_string(), Value::Element(0)),
+                ("offer".to_string(), Value::Element(0)),
+                ("target".to_string(), account_id_to_value(target_account)),
+            ];
+            let recipient = Recipient::client(target_account_id)?;
+            let miden_note = compile_client_note(
+                account_id,
+                &recipient,
+                include_str!("../masm/notes/desk_quote_response.masm"),
+                inputs,
+                vec![],
+            )?;
+
+            Ok(MosaicNote {
+                status: MosaicNoteStatus::New,
+                recipient,
+                order,
+                miden_note,
+            })
+        }
+        Order::LimitBuyOrderLocked {
+            uuid,
+            ref target_account_id,
+            ..
+        }
+        | Order::LimitBuyOrderNotLocked {
+            uuid,
+            ref target_account_id,
+            ..
+        }
+        | Order::LimitSellOrderLocked {
+            uuid,
+            ref target_account_id,
+            ..
+        }
+        | Order::LimitSellOrderNotLocked {
+            uuid,
+            ref target_account_id,
+            ..
+        } => {
+            let (side, locked) = match order {
+                Order::LimitBuyOrderLocked { .. } => (Side::BUY, 1),
+                Order::LimitBuyOrderNotLocked { .. } => (Side::BUY, 0),
+                Order::LimitSellOrderLocked { .. } => (Side::SELL, 1),
+                _ => (Side::SELL, 0),
             };
-            let secret = Word::default();
-            let uuid_high = (uuid >> 64) as u64;
-            let uuid_low = uuid as u64;
-            let side: u64 = if side == Side::SELL { 0 } else { 1 };
+            let target_account = parse_account_id(target_account_id)?;
+            let inputs = vec![
+                ("uuid".to_string(), uuid_to_value(uuid)),
+                ("target".to_string(), account_id_to_value(target_account)),
+                ("side".to_string(), side_to_value(side)),
+                ("locked".to_string(), Value::Element(locked)),
+            ];
+            let recipient = Recipient::client(target_account_id)?;
+            let miden_note = compile_client_note(
+                account_id,
+                &recipient,
+                include_str!("../masm/notes/desk_limit_order_response.masm"),
+                inputs,
+                vec![],
+            )?;
+
+            Ok(MosaicNote {
+                status: MosaicNoteStatus::New,
+                recipient,
+                order,
+                miden_note,
+            })
+        }
+        Order::Settle {
+            ref market,
+            uuid,
+            side,
+            amount,
+            price,
+            ref target_account_id,
+            fee,
+            nonce,
+        } => {
+            let scale = backing.ok_or("Settlement notes need the decimals of the market")?;
+            let target_account = parse_account_id(target_account_id)?;
+
+            // The desk delivers what the client takes, and asks for what the client gives
+            let (delivered, requested) =
+                settlement_assets(market, scale, side, amount, price, fee)?;
+
+            // Must match the note rebuilt by `compile_settlement_payback`
+            let payback_recipient = mosaic_miden::note::p2id_recipient_digest(
+                account_id,
+                payback_serial(uuid, amount, price, nonce),
+            )?;
+            let payback_details = [
+                Felt::from(NoteExecutionHint::always()).as_int(),
+                Felt::from(miden_objects::note::NoteType::Private).as_int(),
+                0,
+                Felt::from(NoteTag::from_account_id(account_id)).as_int(),
+            ];
+
+            let inputs = vec![
+                (
+                    "requested_asset".to_string(),
+                    word_to_value(requested.into()),
+                ),
+                (
+                    "payback_recipient".to_string(),
+                    word_to_value(payback_recipient),
+                ),
+                ("payback_details".to_string(), Value::Word(payback_details)),
+                ("uuid".to_string(), uuid_to_value(uuid)),
+                ("target".to_string(), account_id_to_value(target_account)),
+            ];
+            let recipient = Recipient::client(target_account_id)?;
+            let miden_note = compile_client_note(
+                account_id,
+                &recipient,
+                include_str!("../masm/notes/desk_settlement.masm"),
+                inputs,
+                vec![delivered],
+            )?;
+
+            Ok(MosaicNote {
+                status: MosaicNoteStatus::New,
+                recipient,
+                order,
+                miden_note,
+            })
+        }
+        Order::QuoteRequest {
+            uuid, side, amount, ..
+        } => {
+            // A quote request carries no limit, the desk answers with a firm price
+            let inputs = vec![
+                ("uuid".to_string(), uuid_to_value(uuid)),
+                ("amount".to_string(), Value::Element(amount.units())),
+                ("price".to_string(), Value::Element(0)),
+                ("side".to_string(), side_to_value(side)),
+            ];
+            let recipient = desk_recipient(desk)?;
+            let miden_note = compile_desk_note(
+                account_id,
+                &recipient,
+                include_str!("../masm/notes/client_request_for_quote.masm"),
+                inputs,
+                vec![],
+            )?;
+
+            Ok(MosaicNote {
+                status: MosaicNoteStatus::New,
+                recipient,
+                order,
+                miden_note,
+            })
+        }
+        // Liquidity offers and limit orders rest in the desk book alike
+        Order::LimitOrder {
+            ref market,
+            uuid,
+            side,
+            amount,
+            price,
+            time_in_force,
+        }
+        | Order::LiquidityOffer {
+            ref market,
+            uuid,
+            side,
+            amount,
+            price,
+            time_in_force,
+        } => {
             let inputs = vec![
-                ("uuid".to_string(), Value::Word([uuid_high, uuid_low, 0, 0])),
-                ("amount".to_string(), Value::Element(amount)),
-                ("price".to_string(), Value::Element(price)),
-                ("side".to_string(), Value::Element(side)),
+                ("uuid".to_string(), uuid_to_value(uuid)),
+                ("amount".to_string(), Value::Element(amount.units())),
+                ("price".to_string(), Value::Element(price.units())),
+                ("side".to_string(), side_to_value(side)),
+                (
+                    "expiry".to_string(),
+                    Value::Element(time_in_force.expiry_block() as u64),
+                ),
+                (
+                    "ioc".to_string(),
+                    Value::Element(time_in_force.is_immediate() as u64),
+                ),
             ];
-            let miden_note: MidenNote =
-                mosaic_miden::note::compile_note(abs_note, account_id, secret, inputs)?;
+            let assets = match backing {
+                Some(scale) => vec![offered_asset(market, scale, side, amount, price)?],
+                None => vec![],
+            };
+            let recipient = desk_recipient(desk)?;
+            let miden_note = compile_desk_note(
+                account_id,
+                &recipient,
+                include_str!("../masm/notes/book_offer.masm"),
+                inputs,
+                assets,
+            )?;
 
-            // For LiquidityOffer, the recipient will be the desk
-            // We use a placeholder format for now: "desk:<market>"
-            let recipient_id = format!("desk:{}", market);
+            Ok(MosaicNote {
+                status: MosaicNoteStatus::New,
+                recipient,
+                order,
+                miden_note,
+            })
+        }
+        Order::CancelOffer {
+            uuid,
+            side,
+            entry_id,
+            ..
+        } => {
+            let inputs = vec![
+                ("uuid".to_string(), uuid_to_value(uuid)),
+                (
+                    "entry_id".to_string(),
+                    Value::Element(entry_id.unwrap_or(0)),
+                ),
+                ("side".to_string(), side_to_value(side)),
+            ];
+            let recipient = desk_recipient(desk)?;
+            let miden_note = compile_desk_note(
+                account_id,
+                &recipient,
+                include_str!("../masm/notes/lp_cancel_offer.masm"),
+                inputs,
+                vec![],
+            )?;
 
             Ok(MosaicNote {
                 status: MosaicNoteStatus::New,
-                recipient: Recipient::AccountId(recipient_id),
+                recipient,
                 order,
                 miden_note,
             })
@@ -139,109 +975,606 @@ pub fn compile_note_from_account_id(
             ref target_account_id,
             amount,
         } => {
-            // Parse target account ID from bech32
-            let (_network_id, address) =
-                miden_objects::address::Address::from_bech32(target_account_id)?;
-            let target_account = match address {
-                miden_objects::address::Address::AccountId(account_id_addr) => account_id_addr.id(),
-                _ => {
-                    return Err(format!(
-                        "Invalid address type for target account ID: {}",
-                        target_account_id
-                    )
-                    .into());
-                }
-            };
+            let target_account = parse_account_id(target_account_id)?;
 
             // Create RpoRandomCoin for note creation
-            use miden_objects::Word;
-            use miden_objects::crypto::rand::RpoRandomCoin;
-            // Create a random seed using rand
-            use rand::Rng;
-            let mut thread_rng = rand::rng();
-            let seed = Word::from([
-                thread_rng.random::<u32>(),
-                thread_rng.random::<u32>(),
-                thread_rng.random::<u32>(),
-                thread_rng.random::<u32>(),
-            ]);
-            let mut rng = RpoRandomCoin::new(seed);
+            let mut rng = RpoRandomCoin::new(random_word());
 
+            let recipient = Recipient::client(target_account_id)?;
             let miden_note: MidenNote = mosaic_miden::note::compile_p2id_note(
                 account_id,
                 target_account,
-                amount,
+                amount.units(),
                 &mut rng,
             )?;
 
             Ok(MosaicNote {
                 status: MosaicNoteStatus::New,
-                recipient: Recipient::AccountId(target_account_id.clone()),
+                recipient,
                 order,
                 miden_note,
             })
         }
-        _ => todo!(),
-    }
-}
-
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     use miden_lib::testing::mock_account::MockAccountExt;
-//     use miden_objects::testing::noop_auth_component::NoopAuthComponent;
-
-//     // Mock Account for testing
-//     fn create_mock_account() -> Account<()> {
-//         let miden_account = miden_client::account::Account::mock(
-//             12345u128,         // Simple account ID
-//             NoopAuthComponent, // Simple no-op auth component
-//         );
-//         Account {
-//             miden_account,
-//             miden_client,
-//         }
-//     }
-
-//     #[test]
-//     fn test_compile_note_liquidity_offer() {
-//         let account = create_mock_account();
-//         let market = "BTC/USD".to_string();
-//         let uuid = 123456789u128;
-//         let amount = 1000u64;
-//         let price = 50000u64;
-
-//         let order = Order::LiquidityOffer {
-//             market: market.clone(),
-//             uuid,
-//             amount,
-//             price,
-//         };
-//         let result = compile_note(account, order);
-//         assert!(
-//             result.is_ok(),
-//             "compile_note should succeed for LiquidityOffer"
-//         );
-//         let mosaic_note = result.unwrap();
-//         assert_eq!(mosaic_note.market, market, "Market should match input");
-//     }
-
-//     #[test]
-//     fn test_compile_note_uuid_splitting() {
-//         // Test that UUID is correctly split into high and low parts
-//         let account = create_mock_account();
-//         let uuid: u128 = (u64::MAX as u128) << 64 | (u64::MAX as u128);
-
-//         let order = Order::LiquidityOffer {
-//             market: "ETH/USD".to_string(),
-//             uuid,
-//             amount: 500,
-//             price: 3000,
-//         };
-//         let result = compile_note(account, order);
-//         assert!(
-//             result.is_ok(),
-//             "compile_note should handle large UUID values"
-//         );
-//     }
-// }
+        Order::Transfer { .. } => {
+            Err("Transfers are paid from the wallet vault, they must be committed".into())
+        }
+        Order::KYCPassed { .. } => Err("KYCPassed notes are not compiled yet".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{
+        consume, consume_with_output, miden_note, mock_desk, mock_desk_with_client_assets,
+        mock_market, to_bech32, to_note,
+    };
+    use miden_objects::Felt;
+
+    const SELL_BOOK_SLOT: u8 = 6;
+    const SELL_HEAD_SLOT: u8 = 7;
+    const SELL_REQUESTS_SLOT: u8 = 8;
+    const BUY_BOOK_SLOT: u8 = 11;
+    const BUY_HEAD_SLOT: u8 = 12;
+    const BUY_REQUESTS_SLOT: u8 = 13;
+
+    fn uuid_key(uuid: UUID) -> Word {
+        Word::from([
+            Felt::new((uuid >> 64) as u64),
+            Felt::new(uuid as u64),
+            Felt::new(0),
+            Felt::new(0),
+        ])
+    }
+
+    #[test]
+    fn test_compile_quote_request_note() {
+        let account_id = AccountId::try_from(
+            miden_objects::testing::account_id::ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        )
+        .unwrap();
+        let uuid: UUID = (7 << 64) | 42;
+
+        for (side, encoded) in [(Side::SELL, 0u64), (Side::BUY, 1u64)] {
+            let order = Order::QuoteRequest {
+                market: mock_market().0,
+                uuid,
+                side,
+                amount: Amount::new(250),
+            };
+            let mosaic_note = compile_note_from_account_id(account_id, order.clone()).unwrap();
+            assert_eq!(mosaic_note.order, order);
+            assert_eq!(mosaic_note.recipient, Recipient::broadcast());
+
+            let note = to_note(&mosaic_note);
+            let inputs: Vec<u64> = note
+                .recipient()
+                .inputs()
+                .values()
+                .iter()
+                .map(|felt| felt.as_int())
+                .collect();
+            assert_eq!(inputs, vec![7, 42, 0, 0, 250, 0, encoded]);
+        }
+    }
+
+    #[test]
+    fn test_order_quantities_can_be_given_as_decimals() {
+        let market = serde_json::to_value(mock_market().0).unwrap();
+        let order: Order = serde_json::from_value(serde_json::json!({
+            "LimitOrder": {"market": market, "uuid": 1, "side": "BUY"}
+        }))
+        .unwrap();
+        assert!(order.check_quantities().is_err());
+
+        let scale = Scale {
+            base_decimals: 8,
+            quote_decimals: 6,
+        };
+        let amount = Amount::parse("0.5", scale).unwrap();
+        let order = order.with_quantities(Some(amount), None).unwrap();
+        assert!(order.check_quantities().is_err());
+        let price = Price::parse("65000.25", scale).unwrap();
+        let order = order.with_quantities(None, Some(price)).unwrap();
+        order.check_quantities().unwrap();
+    }
+
+    #[test]
+    fn test_transfer_is_not_compiled() {
+        let account_id = AccountId::try_from(
+            miden_objects::testing::account_id::ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        )
+        .unwrap();
+        let order = Order::Transfer {
+            target_account_id: to_bech32(account_id),
+            asset: mock_market().0.base.issuer,
+            amount: Amount::new(100),
+            reclaim_after: Some(10),
+        };
+        let err = compile_note_from_account_id(account_id, order).unwrap_err();
+        assert!(err.to_string().contains("must be committed"));
+    }
+
+    #[test]
+    fn test_kyc_passed_is_not_compiled() {
+        let account_id = AccountId::try_from(
+            miden_objects::testing::account_id::ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        )
+        .unwrap();
+        let order = Order::KYCPassed {
+            market: mock_market().0,
+        };
+        let err = compile_note_from_account_id(account_id, order).unwrap_err();
+        assert!(err.to_string().contains("not compiled yet"));
+    }
+
+    #[test]
+    fn test_quote_request_consumed_by_desk() {
+        let (chain, mut desk, client_id) = mock_desk();
+
+        for (uuid, side, slot) in [
+            (1, Side::SELL, SELL_REQUESTS_SLOT),
+            (2, Side::BUY, BUY_REQUESTS_SLOT),
+        ] {
+            let order = Order::QuoteRequest {
+                market: mock_market().0,
+                uuid,
+                side,
+                amount: Amount::new(250),
+            };
+            let mosaic_note = compile_note_from_account_id(client_id, order).unwrap();
+            consume(&chain, &mut desk, to_note(&mosaic_note)).unwrap();
+
+            let request = desk.storage().get_map_item(slot, uuid_key(uuid)).unwrap();
+            assert_eq!(
+                request,
+                Word::from([Felt::new(250), Felt::new(0), Felt::new(0), Felt::new(0)])
+            );
+        }
+
+        // Quote requests are keyed by intent and cannot be replayed
+        let order = Order::QuoteRequest {
+            market: mock_market().0,
+            uuid: 1,
+            side: Side::SELL,
+            amount: Amount::new(100),
+        };
+        let mosaic_note = compile_note_from_account_id(client_id, order).unwrap();
+        assert!(consume(&chain, &mut desk, to_note(&mosaic_note)).is_err());
+    }
+
+    #[test]
+    fn test_desk_addressed_note_carries_desk_tag() {
+        let (chain, mut desk, client_id) = mock_desk();
+        let order = Order::QuoteRequest {
+            market: mock_market().0,
+            uuid: 3,
+            side: Side::BUY,
+            amount: Amount::new(10),
+        };
+
+        let mosaic_note =
+            compile_desk_note_from_account_id(client_id, order.clone(), &to_bech32(desk.id()))
+                .unwrap();
+        assert_eq!(mosaic_note.recipient, Recipient::Desk(to_bech32(desk.id())));
+        assert_eq!(
+            to_note(&mosaic_note).metadata().tag(),
+            NoteTag::from_account_id(desk.id())
+        );
+        mosaic_note.check_desk_recipient(desk.id()).unwrap();
+        assert!(mosaic_note.check_desk_recipient(client_id).is_err());
+        assert!(
+            mosaic_note
+                .recipient
+                .check_network(NetworkId::Mainnet)
+                .is_err()
+        );
+
+        // Broadcast notes are accepted by any desk
+        let broadcast = compile_note_from_account_id(client_id, order).unwrap();
+        broadcast.check_desk_recipient(desk.id()).unwrap();
+        broadcast.check_desk_recipient(client_id).unwrap();
+
+        // A note with a forged recipient is rejected
+        let mut forged = broadcast.clone();
+        forged.recipient = Recipient::Desk(to_bech32(desk.id()));
+        assert!(forged.check_desk_recipient(desk.id()).is_err());
+
+        consume(&chain, &mut desk, to_note(&mosaic_note)).unwrap();
+    }
+
+    #[test]
+    fn test_legacy_recipients_are_read_back() {
+        use miden_objects::testing::account_id::ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE;
+
+        // Account funding targets were clients
+        let client = to_bech32(
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE).unwrap(),
+        );
+        let legacy = format!("{{\"AccountId\":\"{}\"}}", client);
+        let recipient: Recipient = serde_json::from_str(&legacy).unwrap();
+        assert_eq!(recipient, Recipient::Client(client.clone()));
+        assert_eq!(
+            serde_json::to_string(&recipient).unwrap(),
+            format!("{{\"Client\":\"{}\"}}", client)
+        );
+
+        // Liquidity offers were addressed to a desk placeholder
+        let legacy = "{\"AccountId\":\"desk:BTC/USDC\"}";
+        let recipient: Recipient = serde_json::from_str(legacy).unwrap();
+        assert_eq!(recipient, Recipient::broadcast());
+
+        let desk = Recipient::Desk(client);
+        let stored = serde_json::to_string(&desk).unwrap();
+        assert_eq!(serde_json::from_str::<Recipient>(&stored).unwrap(), desk);
+    }
+
+    #[test]
+    fn test_desk_status_note_consumed_by_desk() {
+        let (chain, mut desk, owner_id) = mock_desk();
+        let note = compile_desk_status_note(owner_id, &to_bech32(desk.id()), 5, DeskStatus::Paused)
+            .unwrap();
+        assert_eq!(
+            miden_note(&note).metadata().tag(),
+            NoteTag::from_account_id(desk.id())
+        );
+        consume(&chain, &mut desk, miden_note(&note)).unwrap();
+        assert_eq!(crate::book::read_status(&desk).unwrap(), DeskStatus::Paused);
+    }
+
+    #[test]
+    fn test_quote_response_consumed_by_client() {
+        let (chain, desk, client_id) = mock_desk();
+        let client = chain.committed_account(client_id).unwrap().clone();
+
+        let responses = [
+            Order::QuoteRequestOffer {
+                market: mock_market().0,
+                uuid: 1,
+                side: Side::BUY,
+                amount: Amount::new(250),
+                price: Price::new(65_000),
+                target_account_id: to_bech32(client_id),
+            },
+            Order::QuoteRequestNoOffer {
+                market: mock_market().0,
+                uuid: 2,
+                target_account_id: to_bech32(client_id),
+            },
+        ];
+
+        for order in responses {
+            let mosaic_note = compile_note_from_account_id(desk.id(), order).unwrap();
+            assert_eq!(
+                mosaic_note.recipient,
+                Recipient::Client(to_bech32(client_id))
+            );
+
+            // Only the requesting client can consume the response
+            let mut other = desk.clone();
+            assert!(consume(&chain, &mut other, to_note(&mosaic_note)).is_err());
+
+            let mut client = client.clone();
+            consume(&chain, &mut client, to_note(&mosaic_note)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_limit_order_rests_in_book() {
+        let (chain, mut desk, client_id) = mock_desk();
+
+        let orders = [
+            (1, Side::SELL, 65_100, SELL_BOOK_SLOT, SELL_HEAD_SLOT),
+            (2, Side::BUY, 64_900, BUY_BOOK_SLOT, BUY_HEAD_SLOT),
+        ];
+        for (uuid, side, price, book_slot, head_slot) in orders {
+            let order = Order::LimitOrder {
+                market: mock_market().0,
+                uuid,
+                side,
+                amount: Amount::new(10),
+                price: Price::new(price),
+                time_in_force: TimeInForce::GTC,
+            };
+            let mosaic_note = compile_note_from_account_id(client_id, order).unwrap();
+            consume(&chain, &mut desk, to_note(&mosaic_note)).unwrap();
+
+            // First entry of each side gets id 1 and becomes the head
+            let head = desk.storage().get_item(head_slot).unwrap();
+            assert_eq!(head[0], Felt::new(1));
+            let id_key = Word::from([Felt::new(1), Felt::new(0), Felt::new(0), Felt::new(0)]);
+            let entry = desk.storage().get_map_item(book_slot, id_key).unwrap();
+            assert_eq!(
+                entry,
+                Word::from([Felt::new(10), Felt::new(price), Felt::new(0), Felt::new(0)])
+            );
+        }
+
+        let client = chain.committed_account(client_id).unwrap().clone();
+        let acknowledgements = [
+            Order::LimitSellOrderLocked {
+                market: mock_market().0,
+                uuid: 1,
+                target_account_id: to_bech32(client_id),
+            },
+            Order::LimitBuyOrderNotLocked {
+                market: mock_market().0,
+                uuid: 2,
+                target_account_id: to_bech32(client_id),
+            },
+        ];
+        for order in acknowledgements {
+            let mosaic_note = compile_note_from_account_id(desk.id(), order).unwrap();
+            let mut client = client.clone();
+            consume(&chain, &mut client, to_note(&mosaic_note)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_backed_offer_moves_asset_to_desk() {
+        let (chain, mut desk, sender) = mock_desk();
+        let (market, base, quote) = mock_market();
+
+        let offers = [(1, Side::SELL, 100), (2, Side::BUY, 90)];
+        for (uuid, side, price) in offers {
+            let order = Order::LiquidityOffer {
+                market: market.clone(),
+                uuid,
+                side,
+                amount: Amount::new(5),
+                price: Price::new(price),
+                time_in_force: TimeInForce::GTC,
+            };
+            let mosaic_note =
+                compile_backed_note_from_account_id(sender, order, None, Scale::default()).unwrap();
+            consume(&chain, &mut desk, to_note(&mosaic_note)).unwrap();
+        }
+
+        // Sellers lock base, buyers lock quote for the full notional
+        let vault = desk.vault();
+        assert_eq!(vault.get_balance(base).unwrap(), 5);
+        assert_eq!(vault.get_balance(quote).unwrap(), 450);
+
+        let request = Order::QuoteRequest {
+            market: market.clone(),
+            uuid: 3,
+            side: Side::BUY,
+            amount: Amount::new(1),
+        };
+        assert!(
+            compile_backed_note_from_account_id(sender, request, None, Scale::default()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cancelled_backed_offers_are_refunded() {
+        let (chain, mut desk, sender) = mock_desk();
+        let (market, base, quote) = mock_market();
+        let desk_id = desk.id();
+        let offer = |uuid, side, amount, price, scale| {
+            let order = Order::LiquidityOffer {
+                market: market.clone(),
+                uuid,
+                side,
+                amount: Amount::new(amount),
+                price: Price::new(price),
+                time_in_force: TimeInForce::GTC,
+            };
+            let mosaic_note =
+                compile_backed_note_from_account_id(sender, order, None, scale).unwrap();
+            to_note(&mosaic_note)
+        };
+        let rebuild_refund = |uuid, side, amount, price, remaining| {
+            let order = Order::LiquidityOffer {
+                market: market.clone(),
+                uuid,
+                side,
+                amount: Amount::new(amount),
+                price: Price::new(price),
+                time_in_force: TimeInForce::GTC,
+            };
+            let refund = compile_offer_refund(
+                desk_id,
+                sender,
+                &order,
+                Amount::new(remaining),
+                Scale::default(),
+            )
+            .unwrap();
+            miden_note(&refund)
+        };
+        let cancel = |uuid, side| {
+            let order = Order::CancelOffer {
+                market: market.clone(),
+                uuid,
+                side,
+                entry_id: None,
+            };
+            to_note(&compile_note_from_account_id(sender, order).unwrap())
+        };
+
+        // The asset must be the one offered, on the scale of the desk
+        let scale = Scale {
+            base_decimals: 1,
+            quote_decimals: 0,
+        };
+        assert!(consume(&chain, &mut desk, offer(1, Side::BUY, 5, 90, scale)).is_err());
+
+        consume(
+            &chain,
+            &mut desk,
+            offer(1, Side::SELL, 5, 100, Scale::default()),
+        )
+        .unwrap();
+        consume(
+            &chain,
+            &mut desk,
+            offer(2, Side::BUY, 5, 90, Scale::default()),
+        )
+        .unwrap();
+        assert!(
+            crate::book::read_book(&desk, NetworkId::Testnet)
+                .unwrap()
+                .asks[0]
+                .backed
+        );
+
+        // A partially filled offer is refunded what remains
+        let taker = Order::LiquidityOffer {
+            market: market.clone(),
+            uuid: 3,
+            side: Side::BUY,
+            amount: Amount::new(2),
+            price: Price::new(100),
+            time_in_force: TimeInForce::IOC,
+        };
+        let taker = compile_note_from_account_id(sender, taker).unwrap();
+        consume(&chain, &mut desk, to_note(&taker)).unwrap();
+
+        let output = consume_with_output(&chain, &mut desk, cancel(1, Side::SELL)).unwrap();
+        let refund = rebuild_refund(1, Side::SELL, 5, 100, 3);
+        assert_eq!(output, vec![refund.id()]);
+        assert_eq!(desk.vault().get_balance(base).unwrap(), 2);
+
+        let output = consume_with_output(&chain, &mut desk, cancel(2, Side::BUY)).unwrap();
+        let refund = rebuild_refund(2, Side::BUY, 5, 90, 5);
+        assert_eq!(output, vec![refund.id()]);
+        assert_eq!(desk.vault().get_balance(quote).unwrap(), 0);
+
+        // The account that placed the offer rebuilds the refund to collect it
+        let mut wallet = chain.committed_account(sender).unwrap().clone();
+        consume(&chain, &mut wallet, refund).unwrap();
+        assert_eq!(wallet.vault().get_balance(quote).unwrap(), 450);
+    }
+
+    #[test]
+    fn test_expired_and_immediate_backed_offers_are_refunded() {
+        let (mut chain, mut desk, sender) = mock_desk();
+        chain.prove_next_block().unwrap();
+        let block_num = chain.latest_block_header().block_num().as_u32();
+        let (market, base, quote) = mock_market();
+        let desk_id = desk.id();
+        let order = |uuid, side, amount, price, time_in_force| Order::LiquidityOffer {
+            market: market.clone(),
+            uuid,
+            side,
+            amount: Amount::new(amount),
+            price: Price::new(price),
+            time_in_force,
+        };
+        let backed = |order| {
+            let mosaic_note =
+                compile_backed_note_from_account_id(sender, order, None, Scale::default()).unwrap();
+            to_note(&mosaic_note)
+        };
+        let refund = |order: &Order, remaining| {
+            let refund = compile_offer_refund(
+                desk_id,
+                sender,
+                order,
+                Amount::new(remaining),
+                Scale::default(),
+            )
+            .unwrap();
+            miden_note(&refund).id()
+        };
+        let gtd = TimeInForce::GTD {
+            expiry_block: block_num + 2,
+        };
+
+        let maker = order(1, Side::SELL, 5, 100, gtd);
+        consume(&chain, &mut desk, backed(maker)).unwrap();
+
+        // The remainder of an immediate or cancel offer is refunded right away
+        let taker = order(2, Side::BUY, 7, 100, TimeInForce::IOC);
+        let output = consume_with_output(&chain, &mut desk, backed(taker.clone())).unwrap();
+        assert_eq!(output, vec![refund(&taker, 2)]);
+        assert_eq!(desk.vault().get_balance(base).unwrap(), 5);
+        assert_eq!(desk.vault().get_balance(quote).unwrap(), 500);
+
+        // Expired offers are refunded when pruned
+        let expiring = order(3, Side::SELL, 3, 110, gtd);
+        consume(&chain, &mut desk, backed(expiring.clone())).unwrap();
+        chain.prove_until_block(block_num + 2).unwrap();
+
+        let bid = order(4, Side::BUY, 1, 120, TimeInForce::GTC);
+        let bid = to_note(&compile_note_from_account_id(sender, bid).unwrap());
+        let output = consume_with_output(&chain, &mut desk, bid).unwrap();
+        assert_eq!(output, vec![refund(&expiring, 3)]);
+        assert_eq!(desk.vault().get_balance(base).unwrap(), 5);
+        assert!(
+            crate::book::read_book(&desk, NetworkId::Testnet)
+                .unwrap()
+                .asks
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_settlement_swaps_assets_with_client() {
+        let (market, base, quote) = mock_market();
+        let funds = FungibleAsset::new(quote, 1_000).unwrap().into();
+        let (chain, desk, client_id) = mock_desk_with_client_assets(vec![funds]);
+        let client = chain.committed_account(client_id).unwrap().clone();
+        let scale = Scale {
+            base_decimals: 1,
+            quote_decimals: 0,
+        };
+
+        let settle = |amount, fee, nonce| Order::Settle {
+            market: market.clone(),
+            uuid: 1,
+            side: Side::BUY,
+            amount: Amount::parse(amount, scale).unwrap(),
+            price: Price::parse("300", scale).unwrap(),
+            target_account_id: to_bech32(client_id),
+            fee,
+            nonce,
+        };
+
+        // Settlement needs the market faucets
+        assert!(compile_note_from_account_id(desk.id(), settle("2", 15, 1)).is_err());
+
+        let mosaic_note =
+            compile_backed_note_from_account_id(desk.id(), settle("2", 15, 1), None, scale)
+                .unwrap();
+        let note = to_note(&mosaic_note);
+        assert_eq!(note.assets().num_assets(), 1);
+
+        // Only the client can settle
+        let mut other = desk.clone();
+        assert!(consume(&chain, &mut other, note.clone()).is_err());
+
+        // The client buys 2 BTC at 300 USDC, with one decimal for BTC, plus the fee
+        let mut buyer = client.clone();
+        consume(&chain, &mut buyer, note.clone()).unwrap();
+        assert_eq!(buyer.vault().get_balance(base).unwrap(), 20);
+        assert_eq!(buyer.vault().get_balance(quote).unwrap(), 385);
+
+        // The desk rebuilds the note the client paid with, and collects it
+        let payback = compile_settlement_payback(desk.id(), &settle("2", 15, 1), scale).unwrap();
+        let payback = miden_note(&payback);
+        let tx = chain
+            .build_tx_context(client.clone(), &[], &[note])
+            .unwrap()
+            .build()
+            .unwrap()
+            .execute_blocking()
+            .unwrap();
+        assert_eq!(tx.output_notes().get_note(0).id(), payback.id());
+
+        // Another settlement of the same trade pays back with another note
+        let other = compile_settlement_payback(desk.id(), &settle("2", 15, 2), scale).unwrap();
+        assert_ne!(miden_note(&other).id(), payback.id());
+
+        let mut desk = desk.clone();
+        consume(&chain, &mut desk, payback).unwrap();
+        assert_eq!(desk.vault().get_balance(quote).unwrap(), 615);
+
+        // Without enough USDC to pay the desk, nothing moves
+        let mosaic_note =
+            compile_backed_note_from_account_id(desk.id(), settle("4", 0, 2), None, scale).unwrap();
+        let mut buyer = client.clone();
+        assert!(consume(&chain, &mut buyer, to_note(&mosaic_note)).is_err());
+    }
+}
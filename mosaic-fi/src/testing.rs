@@ -0,0 +1,160 @@
+//! Mock chain helpers to execute Mosaic notes against a desk account
+
+use crate::book::{DeskStatus, FeeSchedule, OrderLimits, base_unit_word};
+use crate::note::{MosaicNote, Scale};
+use miden_client::note::{Note, NoteId};
+use miden_client::testing::{Auth, MockChain};
+use miden_lib::account::{auth::NoAuth, wallets::BasicWallet};
+use miden_lib::utils::Deserializable;
+use miden_objects::Word;
+use miden_objects::account::{Account, AccountBuilder, AccountId, AccountStorageMode, AccountType};
+use miden_objects::asset::Asset;
+use miden_objects::testing::account_id::{
+    ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET, ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1,
+};
+use mosaic_miden::client::DeskSettings;
+use mosaic_miden::note::{MidenAbstractNote, MidenNote, NoteType, Value};
+use mosaic_miden::symbol::encode_symbol;
+
+/// Fees of the mock desk
+pub const MOCK_FEES: FeeSchedule = FeeSchedule {
+    maker_bps: 10,
+    taker_bps: 25,
+};
+
+pub fn to_note(mosaic_note: &MosaicNote) -> Note {
+    miden_note(&mosaic_note.miden_note)
+}
+
+pub fn miden_note(miden_note: &MidenNote) -> Note {
+    let bytes = hex::decode(&miden_note.miden_note_hex).unwrap();
+    Note::read_from_bytes(&bytes).unwrap()
+}
+
+/// Execute a transaction consuming the note and apply it to the account
+pub fn consume(chain: &MockChain, account: &mut Account, note: Note) -> Result<(), String> {
+    consume_with_output(chain, account, note).map(|_| ())
+}
+
+/// Execute a transaction consuming the note, apply it to the account and
+/// return the ids of the notes it created
+pub fn consume_with_output(
+    chain: &MockChain,
+    account: &mut Account,
+    note: Note,
+) -> Result<Vec<NoteId>, String> {
+    let tx = chain
+        .build_tx_context(account.clone(), &[], &[note])
+        .map_err(|e| e.to_string())?
+        .build()
+        .map_err(|e| e.to_string())?
+        .execute_blocking()
+        .map_err(|e| e.to_string())?;
+    account
+        .apply_delta(tx.account_delta())
+        .map_err(|e| e.to_string())?;
+    Ok(tx.output_notes().iter().map(|note| note.id()).collect())
+}
+
+/// Build an open desk on a mock chain, along with a client account owning it
+pub fn mock_desk() -> (MockChain, Account, AccountId) {
+    mock_desk_with_client_assets(vec![])
+}
+
+/// Build an open desk on a mock chain, along with a funded client account owning it
+pub fn mock_desk_with_client_assets(assets: Vec<Asset>) -> (MockChain, Account, AccountId) {
+    build_mock_desk(assets, OrderLimits::default())
+}
+
+/// Build an open desk enforcing order limits, on the raw scale of the mock market
+pub fn mock_desk_with_limits(limits: OrderLimits) -> (MockChain, Account, AccountId) {
+    build_mock_desk(vec![], limits)
+}
+
+fn build_mock_desk(assets: Vec<Asset>, limits: OrderLimits) -> (MockChain, Account, AccountId) {
+    let mut builder = MockChain::builder();
+    let client = builder
+        .add_existing_wallet_with_assets(Auth::BasicAuth, assets)
+        .unwrap();
+
+    // The client account owns the desk
+    let (_, base, quote) = mock_market();
+    let book = mosaic_miden::client::desk_book_component(
+        Word::from(encode_symbol("BTC", &base).unwrap()),
+        Word::from(encode_symbol("USDC", &quote).unwrap()),
+        client.id(),
+        DeskSettings {
+            status: DeskStatus::Open.storage_word(),
+            fees: MOCK_FEES.storage_word(),
+            limits: limits.storage_word(Scale::default()).unwrap(),
+            base_unit: base_unit_word(Scale::default()).unwrap(),
+        },
+    )
+    .unwrap();
+    let desk = AccountBuilder::new([7; 32])
+        .account_type(AccountType::RegularAccountImmutableCode)
+        .storage_mode(AccountStorageMode::Public)
+        .with_component(BasicWallet)
+        .with_component(book)
+        .with_auth_component(NoAuth)
+        .build_existing()
+        .unwrap();
+    builder.add_account(desk.clone()).unwrap();
+    let chain = builder.build().unwrap();
+
+    (chain, desk, client.id())
+}
+
+/// Consume a note emitted by the sender that writes the desk status word
+pub fn update_status(
+    chain: &MockChain,
+    desk: &mut Account,
+    sender: AccountId,
+    status: [u64; 4],
+) -> Result<(), String> {
+    let note = mosaic_miden::note::compile_note(
+        MidenAbstractNote {
+            version: mosaic_miden::version::VERSION_STRING.to_string(),
+            note_type: NoteType::Private,
+            program: include_str!("../masm/notes/desk_update_status.masm").to_string(),
+            libraries: vec![(
+                "external_contract::book".to_string(),
+                include_str!("../masm/accounts/book.masm").to_string(),
+            )],
+        },
+        sender,
+        Word::default(),
+        vec![
+            ("intent".to_string(), Value::Word([1, 2, 3, status[0]])),
+            ("status".to_string(), Value::Word(status)),
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    consume(chain, desk, miden_note(&note))
+}
+
+pub fn to_bech32(account_id: AccountId) -> String {
+    let address = miden_objects::address::AccountIdAddress::new(
+        account_id,
+        miden_objects::address::AddressInterface::Unspecified,
+    );
+    miden_objects::address::Address::from(address)
+        .to_bech32(miden_objects::account::NetworkId::Testnet)
+}
+
+/// A BTC/USDC market backed by two mock faucets, returned with the base and quote faucet ids
+pub fn mock_market() -> (crate::Market, AccountId, AccountId) {
+    let base = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap();
+    let quote = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1).unwrap();
+    let market = crate::Market {
+        base: crate::Currency {
+            code: "BTC".to_string(),
+            issuer: to_bech32(base),
+        },
+        quote: crate::Currency {
+            code: "USDC".to_string(),
+            issuer: to_bech32(quote),
+        },
+    };
+    (market, base, quote)
+}
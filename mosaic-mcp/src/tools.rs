@@ -40,7 +40,11 @@ fn derive_secret_from_context(context: &RequestContext<RoleServer>) -> Result<[u
 
     tracing::debug!(user_id = %user_id, "Deriving secret from authenticated user");
 
-    // Derive 32-byte secret from user_id using SHA-256
+    Ok(secret_from_user_id(&user_id))
+}
+
+/// Derive the 32-byte secret of an authenticated user from its user id
+pub fn secret_from_user_id(user_id: &str) -> [u8; 32] {
     use sha2::{Digest, Sha256};
     let mut hasher = Sha256::new();
     hasher.update(user_id.as_bytes());
@@ -48,8 +52,7 @@ fn derive_secret_from_context(context: &RequestContext<RoleServer>) -> Result<[u
 
     let mut secret = [0u8; 32];
     secret.copy_from_slice(&result);
-
-    Ok(secret)
+    secret
 }
 
 fn json_content<T: serde::Serialize>(
@@ -93,7 +96,7 @@ pub struct RegisterAssetResponse {
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct ClientSyncRequest {
-    /// Network: "Testnet" or "Localnet"
+    /// Network: "Testnet", "Devnet", "Mainnet" or "Localnet"
     pub network: String,
 }
 
@@ -103,15 +106,27 @@ fn default_true() -> bool {
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct CreateOrderRequest {
-    /// Network: "Testnet" or "Localnet"
+    /// Network: "Testnet", "Devnet", "Mainnet" or "Localnet"
     pub network: String,
     /// Account ID in bech32 format
     pub account_id: String,
-    /// Order as JSON object (e.g., {"LiquidityOffer": {"market": "BTC/USD", "uuid": 12345, "amount": 1000, "price": 50000}})
+    /// Order as JSON object (e.g., {"LiquidityOffer": {"market": {"base": {"code": "BTC", "issuer": "mtst1..."}, "quote": {"code": "USDC", "issuer": "mtst1..."}}, "uuid": 12345, "amount": 1000, "price": 50000}})
     pub order: mosaic_fi::note::Order,
     /// Whether to commit the note after creation (default: true)
     #[serde(default = "default_true")]
     pub commit: bool,
+    /// Desk account (bech32) the note is addressed to, its market must match the order. Desk orders are broadcast when omitted
+    #[serde(default)]
+    pub desk_account: Option<String>,
+    /// Whether the note carries the offered asset from the faucets of the order market (default: false)
+    #[serde(default)]
+    pub backed: bool,
+    /// Decimal amount of the base asset (e.g. "0.5"), replaces the order amount, which can then be left out
+    #[serde(default)]
+    pub decimal_amount: Option<String>,
+    /// Decimal price in quote per base asset (e.g. "65000.25"), replaces the order price, which can then be left out
+    #[serde(default)]
+    pub decimal_price: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
@@ -127,7 +142,7 @@ pub struct CreateAccountOrderResponse {
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct CreateRawNoteRequest {
-    /// Network: "Testnet" or "Localnet"
+    /// Network: "Testnet", "Devnet", "Mainnet" or "Localnet"
     pub network: String,
     /// Account ID in bech32 format
     pub account_id: String,
@@ -148,7 +163,7 @@ pub struct CreateRawNoteRequest {
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct ConsumeNoteRequest {
-    /// Network: "Testnet" or "Localnet"
+    /// Network: "Testnet", "Devnet", "Mainnet" or "Localnet"
     pub network: String,
     /// Account ID in bech32 format
     pub account_id: String,
@@ -156,9 +171,52 @@ pub struct ConsumeNoteRequest {
     pub miden_note: mosaic_miden::note::MidenNote,
 }
 
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct ListConsumableNotesRequest {
+    /// Network: "Testnet", "Devnet", "Mainnet" or "Localnet"
+    pub network: String,
+    /// Account ID in bech32 format
+    pub account_id: String,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct ConsumeNotesRequest {
+    /// Network: "Testnet", "Devnet", "Mainnet" or "Localnet"
+    pub network: String,
+    /// Account ID in bech32 format
+    pub account_id: String,
+    /// IDs of the notes to consume, in hex format, as listed by list_consumable_notes
+    pub note_ids: Vec<String>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct MintRequest {
+    /// Faucet account ID in bech32 format, owned by the authenticated user
+    pub faucet_account: String,
+    /// Account ID receiving the minted assets, in bech32 format
+    pub target_account: String,
+    /// Amount to mint, in base units of the faucet
+    pub amount: u64,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct TransferRequest {
+    /// Wallet account ID in bech32 format, owned by the authenticated user
+    pub account_id: String,
+    /// Account ID receiving the assets, in bech32 format
+    pub target_account: String,
+    /// Faucet account ID of the asset to transfer, in bech32 format
+    pub asset: String,
+    /// Amount to transfer, in base units of the faucet
+    pub amount: u64,
+    /// Blocks after which the sender can reclaim an unconsumed note (P2IDE)
+    #[serde(default)]
+    pub reclaim_after: Option<u32>,
+}
+
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct GetAccountStatusRequest {
-    /// Network: "Testnet" or "Localnet"
+    /// Network: "Testnet", "Devnet", "Mainnet" or "Localnet"
     pub network: String,
     /// Account ID in bech32 format
     pub account_id: String,
@@ -221,6 +279,43 @@ pub struct DeskPushNoteRequest {
     pub note: mosaic_fi::note::MosaicNote,
 }
 
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct DeskRespondToQuoteRequest {
+    /// Desk account ID in bech32 format
+    pub desk_account: String,
+    /// Identifier of the consumed quote request in the desk's note store
+    pub note_id: i64,
+    /// Firm price offered to the client in quote units per base token, omit to decline the request
+    #[serde(default)]
+    pub price: Option<mosaic_fi::note::Price>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct DeskAcknowledgeLimitOrderRequest {
+    /// Desk account ID in bech32 format
+    pub desk_account: String,
+    /// Identifier of the limit order in the desk's note store
+    pub note_id: i64,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct DeskSettleQuoteRequest {
+    /// Desk account ID in bech32 format
+    pub desk_account: String,
+    /// Identifier of the consumed quote request in the desk's note store
+    pub note_id: i64,
+    /// Firm price the quote request is settled at, in quote units per base token, as offered by desk_respond_to_quote
+    pub price: mosaic_fi::note::Price,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct DeskCollectSettlementRequest {
+    /// Desk account ID in bech32 format
+    pub desk_account: String,
+    /// Settlement note returned by desk_settle_quote, once consumed by the client
+    pub settlement: mosaic_fi::note::MosaicNote,
+}
+
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct GetDeskInfoRequest {
     /// Desk account ID in bech32 format
@@ -280,12 +375,33 @@ pub struct CreateRawNoteResponse {
     pub note: mosaic_miden::note::MidenNote,
 }
 
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct MintResponse {
+    pub success: bool,
+    /// FundAccount note to hand to the target account
+    pub note: mosaic_fi::note::MosaicNote,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct TransferResponse {
+    pub success: bool,
+    /// Transfer note to hand to the target account
+    pub note: mosaic_fi::note::MosaicNote,
+}
+
 #[derive(Debug, serde::Serialize, schemars::JsonSchema)]
 pub struct ConsumeNoteResponse {
     pub success: bool,
     pub transaction_id: String,
 }
 
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct ListConsumableNotesResponse {
+    pub success: bool,
+    pub account_id: String,
+    pub notes: Vec<mosaic_miden::ConsumableNoteData>,
+}
+
 #[derive(Debug, serde::Serialize, schemars::JsonSchema)]
 pub struct DeskPushNoteResponse {
     pub success: bool,
@@ -293,6 +409,34 @@ pub struct DeskPushNoteResponse {
     pub note_id: i64,
 }
 
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct DeskRespondToQuoteResponse {
+    pub success: bool,
+    pub desk_account: String,
+    pub note: mosaic_fi::note::MosaicNote,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct DeskSettleQuoteResponse {
+    pub success: bool,
+    pub desk_account: String,
+    pub note: mosaic_fi::note::MosaicNote,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct DeskCollectSettlementResponse {
+    pub success: bool,
+    pub desk_account: String,
+    pub transaction_id: String,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct DeskAcknowledgeLimitOrderResponse {
+    pub success: bool,
+    pub desk_account: String,
+    pub note: mosaic_fi::note::MosaicNote,
+}
+
 #[derive(Debug, serde::Serialize, schemars::JsonSchema)]
 pub struct GetDeskInfoResponse {
     pub success: bool,
@@ -300,6 +444,12 @@ pub struct GetDeskInfoResponse {
     pub account_id: String,
     pub network: String,
     pub market: mosaic_fi::Market,
+    /// Trading status read from the desk account, when available
+    pub status: Option<mosaic_fi::book::DeskStatus>,
+    /// Maker and taker fees in basis points, read from the desk account when available
+    pub fees: Option<mosaic_fi::book::FeeSchedule>,
+    /// Tick size, lot size and notional bounds, read from the desk account when available
+    pub limits: Option<mosaic_fi::book::OrderLimits>,
 }
 
 #[derive(Debug, serde::Serialize, schemars::JsonSchema)]
@@ -591,10 +741,7 @@ impl Mosaic {
             .into_iter()
             .map(|desk| DeskAccountInfo {
                 account_id: desk.account_id,
-                network: match desk.network {
-                    Network::Testnet => "Testnet".to_string(),
-                    Network::Localnet => "Localnet".to_string(),
-                },
+                network: desk.network.to_string(),
                 market: desk.market,
                 owner_account: desk.owner_account,
                 market_url: desk.market_url,
@@ -624,10 +771,12 @@ impl Mosaic {
         // Parse network
         let network = match req.network.as_str() {
             "Testnet" => Network::Testnet,
+            "Devnet" => Network::Devnet,
+            "Mainnet" => Network::Mainnet,
             "Localnet" => Network::Localnet,
             _ => {
                 let error_msg = format!(
-                    "Invalid network '{}'. Must be 'Testnet' or 'Localnet'",
+                    "Invalid network '{}'. Must be 'Testnet', 'Devnet', 'Mainnet' or 'Localnet'",
                     req.network
                 );
                 tracing::error!(error = %error_msg, network = %req.network, "Invalid network");
@@ -678,7 +827,7 @@ impl Mosaic {
     }
 
     #[tool(
-        description = "Create an order note for the authenticated user's account. Optionally commit it to the network."
+        description = "Create an order note for the authenticated user's account. Amounts and prices are integer units unless given as decimal_amount and decimal_price, in which case the order can leave them out. Optionally commit it to the network."
     )]
     async fn create_order(
         &self,
@@ -691,10 +840,12 @@ impl Mosaic {
         // Parse network
         let network = match req.network.as_str() {
             "Testnet" => Network::Testnet,
+            "Devnet" => Network::Devnet,
+            "Mainnet" => Network::Mainnet,
             "Localnet" => Network::Localnet,
             _ => {
                 let error_msg = format!(
-                    "Invalid network '{}'. Must be 'Testnet' or 'Localnet'",
+                    "Invalid network '{}'. Must be 'Testnet', 'Devnet', 'Mainnet' or 'Localnet'",
                     req.network
                 );
                 tracing::error!(error = %error_msg, network = %req.network, "Invalid network");
@@ -702,14 +853,34 @@ impl Mosaic {
             }
         };
 
-        // Parse order from JSON
-        let order: mosaic_fi::note::Order = req.order;
-
         // Create the note
         let mosaic_note = {
             let mut serve = self.serve.lock().await;
+
+            // Convert decimal quantities with the decimals of the market assets
+            let order = serve
+                .scale_order(
+                    secret,
+                    req.order,
+                    req.decimal_amount.as_deref(),
+                    req.decimal_price.as_deref(),
+                )
+                .map_err(|e| {
+                    let error_msg = format!("Invalid order quantities: {}", e);
+                    tracing::error!(error = %error_msg, account_id = %req.account_id, "Failed to scale order");
+                    McpError::invalid_params(error_msg, None)
+                })?;
+
             serve
-                .create_private_note(secret, network, req.account_id.clone(), order, req.commit)
+                .create_private_note(
+                    secret,
+                    network,
+                    req.account_id.clone(),
+                    order,
+                    req.commit,
+                    req.desk_account.clone(),
+                    req.backed,
+                )
                 .await
                 .map_err(|e| {
                     let error_msg = format!("Failed to create order note: {}", e);
@@ -757,10 +928,12 @@ impl Mosaic {
         // Parse network
         let network = match req.network.as_str() {
             "Testnet" => Network::Testnet,
+            "Devnet" => Network::Devnet,
+            "Mainnet" => Network::Mainnet,
             "Localnet" => Network::Localnet,
             _ => {
                 let error_msg = format!(
-                    "Invalid network '{}'. Must be 'Testnet' or 'Localnet'",
+                    "Invalid network '{}'. Must be 'Testnet', 'Devnet', 'Mainnet' or 'Localnet'",
                     req.network
                 );
                 tracing::error!(error = %error_msg, network = %req.network, "Invalid network");
@@ -845,10 +1018,12 @@ impl Mosaic {
         // Parse network
         let network = match req.network.as_str() {
             "Testnet" => Network::Testnet,
+            "Devnet" => Network::Devnet,
+            "Mainnet" => Network::Mainnet,
             "Localnet" => Network::Localnet,
             _ => {
                 let error_msg = format!(
-                    "Invalid network '{}'. Must be 'Testnet' or 'Localnet'",
+                    "Invalid network '{}'. Must be 'Testnet', 'Devnet', 'Mainnet' or 'Localnet'",
                     req.network
                 );
                 tracing::error!(error = %error_msg, network = %req.network, "Invalid network");
@@ -927,10 +1102,12 @@ impl Mosaic {
         // Parse network
         let network = match req.network.as_str() {
             "Testnet" => Network::Testnet,
+            "Devnet" => Network::Devnet,
+            "Mainnet" => Network::Mainnet,
             "Localnet" => Network::Localnet,
             _ => {
                 let error_msg = format!(
-                    "Invalid network '{}'. Must be 'Testnet' or 'Localnet'",
+                    "Invalid network '{}'. Must be 'Testnet', 'Devnet', 'Mainnet' or 'Localnet'",
                     req.network
                 );
                 tracing::error!(
@@ -985,53 +1162,254 @@ impl Mosaic {
         Ok(CallToolResult::success(vec![content]))
     }
 
-    #[tool(description = "Push a Mosaic note to a desk's note store")]
-    async fn desk_push_note(
+    #[tool(
+        description = "List the notes discovered by client_sync that the authenticated user's account can consume, such as P2ID payments. Notes with consumable_after set can only be consumed after that block."
+    )]
+    async fn list_consumable_notes(
         &self,
-        Parameters(req): Parameters<DeskPushNoteRequest>,
+        Parameters(req): Parameters<ListConsumableNotesRequest>,
         context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
+        // Derive secret from authenticated user
         let secret = derive_secret_from_context(&context)?;
 
-        let desk_account = req.desk_account.clone();
+        // Parse network
+        let network = match req.network.as_str() {
+            "Testnet" => Network::Testnet,
+            "Devnet" => Network::Devnet,
+            "Mainnet" => Network::Mainnet,
+            "Localnet" => Network::Localnet,
+            _ => {
+                let error_msg = format!(
+                    "Invalid network '{}'. Must be 'Testnet', 'Devnet', 'Mainnet' or 'Localnet'",
+                    req.network
+                );
+                tracing::error!(error = %error_msg, network = %req.network, "Invalid network");
+                return Err(McpError::invalid_params(error_msg, None));
+            }
+        };
 
-        // Fetch desk info to validate ownership
-        let (desk_account_id, desk_network, _market) = {
-            let serve = self.serve.lock().await;
-            serve.get_desk_info(&desk_account).await.map_err(|e| {
-                let error_msg = format!("Failed to get desk info: {}", e);
-                tracing::error!(
-                    error = %error_msg,
-                    desk_account = %desk_account,
-                    "Failed to get desk info during desk_push_note"
+        let notes = {
+            let mut serve = self.serve.lock().await;
+            serve
+                .list_consumable_notes(secret, network, req.account_id.clone())
+                .await
+                .map_err(|e| {
+                    let error_msg = format!("Failed to list consumable notes: {}", e);
+                    tracing::error!(
+                        error = %error_msg,
+                        account_id = %req.account_id,
+                        network = %req.network,
+                        "Failed to list consumable notes"
+                    );
+                    McpError::internal_error(error_msg, None)
+                })?
+        };
+
+        tracing::info!(
+            tool = "list_consumable_notes",
+            account_id = %req.account_id,
+            network = %req.network,
+            note_count = notes.len(),
+            "Listed consumable notes"
+        );
+
+        let response = ListConsumableNotesResponse {
+            success: true,
+            account_id: req.account_id,
+            notes,
+        };
+
+        let content = json_content(&response, "list_consumable_notes response")?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Consume notes discovered by client_sync, by note id, using the authenticated user's account. The notes are consumed in a single transaction adding their assets to the account."
+    )]
+    async fn consume_notes(
+        &self,
+        Parameters(req): Parameters<ConsumeNotesRequest>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        // Derive secret from authenticated user
+        let secret = derive_secret_from_context(&context)?;
+
+        // Parse network
+        let network = match req.network.as_str() {
+            "Testnet" => Network::Testnet,
+            "Devnet" => Network::Devnet,
+            "Mainnet" => Network::Mainnet,
+            "Localnet" => Network::Localnet,
+            _ => {
+                let error_msg = format!(
+                    "Invalid network '{}'. Must be 'Testnet', 'Devnet', 'Mainnet' or 'Localnet'",
+                    req.network
                 );
-                McpError::internal_error(error_msg, None)
-            })?
+                tracing::error!(error = %error_msg, network = %req.network, "Invalid network");
+                return Err(McpError::invalid_params(error_msg, None));
+            }
         };
 
-        let accounts = {
-            let serve = self.serve.lock().await;
-            serve.list_accounts(secret).await.map_err(|e| {
-                let error_msg = format!("Failed to list accounts for authorization: {}", e);
-                tracing::error!(error = %error_msg, "Failed to list accounts during desk authorization");
-                McpError::internal_error(error_msg, None)
-            })?
+        let transaction_id = {
+            let mut serve = self.serve.lock().await;
+            serve
+                .consume_notes(
+                    secret,
+                    network,
+                    req.account_id.clone(),
+                    req.note_ids.clone(),
+                )
+                .await
+                .map_err(|e| {
+                    let error_msg = format!("Failed to consume notes: {}", e);
+                    tracing::error!(
+                        error = %error_msg,
+                        account_id = %req.account_id,
+                        network = %req.network,
+                        "Failed to consume notes"
+                    );
+                    McpError::internal_error(error_msg, None)
+                })?
         };
 
-        let owns_desk = accounts
-            .desk_accounts
-            .iter()
-            .any(|desk| desk.account_id == desk_account && desk.network == desk_network);
+        tracing::info!(
+            tool = "consume_notes",
+            account_id = %req.account_id,
+            network = %req.network,
+            note_count = req.note_ids.len(),
+            transaction_id = %transaction_id,
+            "Consumed notes"
+        );
 
-        if !owns_desk {
-            let error_msg = format!("Authenticated user does not own desk {}", desk_account);
-            tracing::warn!(
-                desk_account = %desk_account,
-                account_id = %desk_account_id,
-                "Desk ownership check failed"
-            );
-            return Err(McpError::invalid_request(error_msg, None));
-        }
+        let response = ConsumeNoteResponse {
+            success: true,
+            transaction_id,
+        };
+
+        let content = json_content(&response, "consume_notes response")?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Mint assets from a faucet owned by the authenticated user into a private P2ID note for a target account. The returned note is consumed by the target with consume_note."
+    )]
+    async fn mint(
+        &self,
+        Parameters(req): Parameters<MintRequest>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        // Derive secret from authenticated user
+        let secret = derive_secret_from_context(&context)?;
+
+        let note = {
+            let mut serve = self.serve.lock().await;
+            serve
+                .mint(
+                    secret,
+                    req.faucet_account.clone(),
+                    req.target_account.clone(),
+                    req.amount.into(),
+                )
+                .await
+                .map_err(|e| {
+                    let error_msg = format!("Failed to mint: {}", e);
+                    tracing::error!(
+                        error = %error_msg,
+                        faucet_account = %req.faucet_account,
+                        target_account = %req.target_account,
+                        "Failed to mint"
+                    );
+                    McpError::internal_error(error_msg, None)
+                })?
+        };
+
+        tracing::info!(
+            tool = "mint",
+            faucet_account = %req.faucet_account,
+            target_account = %req.target_account,
+            amount = req.amount,
+            "Minted assets"
+        );
+
+        let response = MintResponse {
+            success: true,
+            note,
+        };
+
+        let content = json_content(&response, "mint response")?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Transfer assets from a wallet owned by the authenticated user into a private P2ID note for a target account. With reclaim_after the note is a P2IDE note the sender can reclaim after that many blocks. The returned note is consumed by the target with consume_note."
+    )]
+    async fn transfer(
+        &self,
+        Parameters(req): Parameters<TransferRequest>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        // Derive secret from authenticated user
+        let secret = derive_secret_from_context(&context)?;
+
+        let note = {
+            let mut serve = self.serve.lock().await;
+            serve
+                .transfer(
+                    secret,
+                    req.account_id.clone(),
+                    req.target_account.clone(),
+                    req.asset.clone(),
+                    req.amount.into(),
+                    req.reclaim_after,
+                )
+                .await
+                .map_err(|e| {
+                    let error_msg = format!("Failed to transfer: {}", e);
+                    tracing::error!(
+                        error = %error_msg,
+                        account_id = %req.account_id,
+                        target_account = %req.target_account,
+                        "Failed to transfer"
+                    );
+                    McpError::internal_error(error_msg, None)
+                })?
+        };
+
+        tracing::info!(
+            tool = "transfer",
+            account_id = %req.account_id,
+            target_account = %req.target_account,
+            asset = %req.asset,
+            amount = req.amount,
+            reclaim_after = ?req.reclaim_after,
+            "Transferred assets"
+        );
+
+        let response = TransferResponse {
+            success: true,
+            note,
+        };
+
+        let content = json_content(&response, "transfer response")?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Push a Mosaic note to a desk's note store")]
+    async fn desk_push_note(
+        &self,
+        Parameters(req): Parameters<DeskPushNoteRequest>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let secret = derive_secret_from_context(&context)?;
+
+        let desk_account = req.desk_account.clone();
+
+        self.authorize_desk_owner(secret, &desk_account).await?;
 
         // Push the note to the desk
         let note_id = {
@@ -1068,7 +1446,206 @@ impl Mosaic {
         Ok(CallToolResult::success(vec![content]))
     }
 
-    #[tool(description = "Get desk information including account ID, network, and market data")]
+    #[tool(
+        description = "Answer a quote request consumed by a desk with a firm price, or decline it when no price is given. Returns the response note for the requesting client to consume."
+    )]
+    async fn desk_respond_to_quote(
+        &self,
+        Parameters(req): Parameters<DeskRespondToQuoteRequest>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let secret = derive_secret_from_context(&context)?;
+
+        let desk_account = req.desk_account.clone();
+
+        self.authorize_desk_owner(secret, &desk_account).await?;
+
+        let note = {
+            let serve = self.serve.lock().await;
+            serve
+                .desk_respond_to_quote(&desk_account, req.note_id, req.price)
+                .await
+                .map_err(|e| {
+                    let error_msg = format!("Failed to answer quote request: {}", e);
+                    tracing::error!(
+                        error = %error_msg,
+                        desk_account = %desk_account,
+                        note_id = req.note_id,
+                        "Failed to answer quote request"
+                    );
+                    McpError::internal_error(error_msg, None)
+                })?
+        };
+
+        tracing::info!(
+            tool = "desk_respond_to_quote",
+            desk_account = %desk_account,
+            note_id = req.note_id,
+            offered = req.price.is_some(),
+            "Answered quote request"
+        );
+
+        let response = DeskRespondToQuoteResponse {
+            success: true,
+            desk_account,
+            note,
+        };
+
+        let content = json_content(&response, "desk_respond_to_quote response")?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Settle a quote request consumed by a desk at the price the desk offered for it. A quote request is settled once. Returns a settlement note the requesting client consumes to receive the traded asset by paying the desk in the same transaction."
+    )]
+    async fn desk_settle_quote(
+        &self,
+        Parameters(req): Parameters<DeskSettleQuoteRequest>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let secret = derive_secret_from_context(&context)?;
+
+        let desk_account = req.desk_account.clone();
+
+        self.authorize_desk_owner(secret, &desk_account).await?;
+
+        let note = {
+            let serve = self.serve.lock().await;
+            serve
+                .desk_settle_quote(&desk_account, req.note_id, req.price)
+                .await
+                .map_err(|e| {
+                    let error_msg = format!("Failed to settle quote request: {}", e);
+                    tracing::error!(
+                        error = %error_msg,
+                        desk_account = %desk_account,
+                        note_id = req.note_id,
+                        "Failed to settle quote request"
+                    );
+                    McpError::internal_error(error_msg, None)
+                })?
+        };
+
+        tracing::info!(
+            tool = "desk_settle_quote",
+            desk_account = %desk_account,
+            note_id = req.note_id,
+            price = %req.price,
+            "Settled quote request"
+        );
+
+        let response = DeskSettleQuoteResponse {
+            success: true,
+            desk_account,
+            note,
+        };
+
+        let content = json_content(&response, "desk_settle_quote response")?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Collect the payment of a settlement note once the client consumed it, moving the paid asset into the desk vault."
+    )]
+    async fn desk_collect_settlement(
+        &self,
+        Parameters(req): Parameters<DeskCollectSettlementRequest>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let secret = derive_secret_from_context(&context)?;
+
+        let desk_account = req.desk_account.clone();
+
+        self.authorize_desk_owner(secret, &desk_account).await?;
+
+        let transaction_id = {
+            let serve = self.serve.lock().await;
+            serve
+                .desk_collect_settlement(&desk_account, &req.settlement)
+                .await
+                .map_err(|e| {
+                    let error_msg = format!("Failed to collect settlement: {}", e);
+                    tracing::error!(
+                        error = %error_msg,
+                        desk_account = %desk_account,
+                        "Failed to collect settlement"
+                    );
+                    McpError::internal_error(error_msg, None)
+                })?
+        };
+
+        tracing::info!(
+            tool = "desk_collect_settlement",
+            desk_account = %desk_account,
+            transaction_id = %transaction_id,
+            "Collected settlement"
+        );
+
+        let response = DeskCollectSettlementResponse {
+            success: true,
+            desk_account,
+            transaction_id,
+        };
+
+        let content = json_content(&response, "desk_collect_settlement response")?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Acknowledge a limit order pushed to a desk. Returns a locked note when the order rests in the book, or a not locked note when the desk refused it."
+    )]
+    async fn desk_acknowledge_limit_order(
+        &self,
+        Parameters(req): Parameters<DeskAcknowledgeLimitOrderRequest>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let secret = derive_secret_from_context(&context)?;
+
+        let desk_account = req.desk_account.clone();
+
+        self.authorize_desk_owner(secret, &desk_account).await?;
+
+        let note = {
+            let serve = self.serve.lock().await;
+            serve
+                .desk_acknowledge_limit_order(&desk_account, req.note_id)
+                .await
+                .map_err(|e| {
+                    let error_msg = format!("Failed to acknowledge limit order: {}", e);
+                    tracing::error!(
+                        error = %error_msg,
+                        desk_account = %desk_account,
+                        note_id = req.note_id,
+                        "Failed to acknowledge limit order"
+                    );
+                    McpError::internal_error(error_msg, None)
+                })?
+        };
+
+        tracing::info!(
+            tool = "desk_acknowledge_limit_order",
+            desk_account = %desk_account,
+            note_id = req.note_id,
+            "Acknowledged limit order"
+        );
+
+        let response = DeskAcknowledgeLimitOrderResponse {
+            success: true,
+            desk_account,
+            note,
+        };
+
+        let content = json_content(&response, "desk_acknowledge_limit_order response")?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Get desk information including account ID, network, market data, trading status (Open, Paused, CancelOnly or Closed), maker/taker fees in basis points (the taker fee is collected on settlements, fees on book fills are not charged and only reported from the schedule recorded with each fill) and order limits (tick size, lot size, min/max notional)"
+    )]
     async fn get_desk_info(
         &self,
         Parameters(req): Parameters<GetDeskInfoRequest>,
@@ -1076,9 +1653,9 @@ impl Mosaic {
         let desk_account = req.desk_account.clone();
 
         // Get desk info
-        let (account_id, network, market) = {
+        let ((account_id, network, market), status, fees, limits) = {
             let serve = self.serve.lock().await;
-            serve.get_desk_info(&desk_account).await.map_err(|e| {
+            let info = serve.get_desk_info(&desk_account).await.map_err(|e| {
                 let error_msg = format!("Failed to get desk info: {}", e);
                 tracing::error!(
                     error = %error_msg,
@@ -1086,7 +1663,11 @@ impl Mosaic {
                     "Failed to get desk info"
                 );
                 McpError::internal_error(error_msg, None)
-            })?
+            })?;
+            let status = serve.desk_get_status(&desk_account).await.ok();
+            let fees = serve.desk_get_fees(&desk_account).await.ok();
+            let limits = serve.desk_get_limits(&desk_account).await.ok();
+            (info, status, fees, limits)
         };
 
         tracing::info!(
@@ -1100,11 +1681,11 @@ impl Mosaic {
             success: true,
             desk_account,
             account_id,
-            network: match network {
-                Network::Testnet => "Testnet".to_string(),
-                Network::Localnet => "Localnet".to_string(),
-            },
+            network: network.to_string(),
             market,
+            status,
+            fees,
+            limits,
         };
 
         let content = json_content(&response, "get_desk_info response")?;
@@ -1165,6 +1746,55 @@ impl Mosaic {
     }
 }
 
+impl Mosaic {
+    /// Ensure the authenticated user owns the given desk
+    async fn authorize_desk_owner(
+        &self,
+        secret: [u8; 32],
+        desk_account: &str,
+    ) -> Result<(), McpError> {
+        // Fetch desk info to validate ownership
+        let (desk_account_id, desk_network, _market) = {
+            let serve = self.serve.lock().await;
+            serve.get_desk_info(desk_account).await.map_err(|e| {
+                let error_msg = format!("Failed to get desk info: {}", e);
+                tracing::error!(
+                    error = %error_msg,
+                    desk_account = %desk_account,
+                    "Failed to get desk info during desk authorization"
+                );
+                McpError::internal_error(error_msg, None)
+            })?
+        };
+
+        let accounts = {
+            let serve = self.serve.lock().await;
+            serve.list_accounts(secret).await.map_err(|e| {
+                let error_msg = format!("Failed to list accounts for authorization: {}", e);
+                tracing::error!(error = %error_msg, "Failed to list accounts during desk authorization");
+                McpError::internal_error(error_msg, None)
+            })?
+        };
+
+        let owns_desk = accounts
+            .desk_accounts
+            .iter()
+            .any(|desk| desk.account_id == desk_account && desk.network == desk_network);
+
+        if !owns_desk {
+            let error_msg = format!("Authenticated user does not own desk {}", desk_account);
+            tracing::warn!(
+                desk_account = %desk_account,
+                account_id = %desk_account_id,
+                "Desk ownership check failed"
+            );
+            return Err(McpError::invalid_request(error_msg, None));
+        }
+
+        Ok(())
+    }
+}
+
 #[prompt_router]
 impl Mosaic {}
 
@@ -1178,7 +1808,7 @@ impl ServerHandler for Mosaic {
                 .enable_tools()
                 .build(),
             server_info: Implementation::from_build_env(),
-            instructions: Some("Mosaic MCP server. Available tools: create_account_order, list_accounts, list_assets, list_orders, get_role_settings, update_role_settings, register_asset, client_sync, create_order, create_raw_note, get_account_status, consume_note, desk_push_note, get_desk_info, flush, version.".to_string()),
+            instructions: Some("Mosaic MCP server. Available tools: create_account_order, list_accounts, list_assets, list_orders, get_role_settings, update_role_settings, register_asset, client_sync, create_order, create_raw_note, get_account_status, consume_note, desk_push_note, desk_respond_to_quote, desk_settle_quote, desk_collect_settlement, desk_acknowledge_limit_order, get_desk_info, flush, version.".to_string()),
         }
     }
 
@@ -0,0 +1,325 @@
+use crate::{ConsumableNoteData, MidenTransactionId, Network, client::ClientHandle};
+use miden_client::{account::AccountId, note::NoteId};
+use miden_objects::{
+    Word,
+    address::{AccountIdAddress, Address, AddressInterface},
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Events buffered for slow subscribers before they start lagging
+const EVENT_CAPACITY: usize = 64;
+
+/// Notes an account consumes as soon as a sync discovers them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConsumeRule {
+    /// P2ID and P2IDE payments to the account
+    Payments,
+    /// Notes running one of the scripts, by script root
+    Scripts(Vec<Word>),
+}
+
+impl ConsumeRule {
+    fn matches(&self, note: &ConsumableNoteData) -> bool {
+        match self {
+            ConsumeRule::Payments => {
+                matches!(note.standard.as_deref(), Some("P2ID") | Some("P2IDE"))
+            }
+            ConsumeRule::Scripts(roots) => {
+                roots.iter().any(|root| root.to_hex() == note.script_root)
+            }
+        }
+    }
+}
+
+/// Rules of the accounts whose notes are consumed automatically
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsumePolicy {
+    rules: BTreeMap<AccountId, Vec<ConsumeRule>>,
+}
+
+impl ConsumePolicy {
+    pub fn with_rule(mut self, account_id: AccountId, rule: ConsumeRule) -> Self {
+        self.add_rule(account_id, rule);
+        self
+    }
+
+    pub fn add_rule(&mut self, account_id: AccountId, rule: ConsumeRule) {
+        let rules = self.rules.entry(account_id).or_default();
+        if !rules.contains(&rule) {
+            rules.push(rule);
+        }
+    }
+
+    pub fn accounts(&self) -> impl Iterator<Item = AccountId> + '_ {
+        self.rules.keys().copied()
+    }
+
+    /// Whether the account consumes the note now
+    ///
+    /// Notes that only become consumable after some block are left for a
+    /// later sync.
+    pub fn matches(&self, account_id: AccountId, note: &ConsumableNoteData) -> bool {
+        note.consumable_after.is_none()
+            && self
+                .rules
+                .get(&account_id)
+                .is_some_and(|rules| rules.iter().any(|rule| rule.matches(note)))
+    }
+}
+
+/// Event emitted by the background sync of a client
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, schemars::JsonSchema)]
+pub enum SyncEvent {
+    Synced {
+        block_num: u32,
+        new_public_notes: u32,
+        committed_notes: u32,
+        consumed_notes: u32,
+        updated_accounts: u32,
+    },
+    SyncFailed {
+        error: String,
+    },
+    NotesConsumed {
+        /// Account ID in bech32 format
+        account_id: String,
+        note_ids: Vec<String>,
+        transaction_id: MidenTransactionId,
+    },
+    ConsumeFailed {
+        /// Account ID in bech32 format
+        account_id: String,
+        note_ids: Vec<String>,
+        error: String,
+    },
+}
+
+/// Background task syncing a client on an interval
+///
+/// After each successful sync, the notes matching the consume policy are
+/// consumed, one transaction per account. Notes that failed to be consumed
+/// are not retried. The task stops when dropped or when the client shuts down.
+pub struct AutoSync {
+    policy: Arc<RwLock<ConsumePolicy>>,
+    events: broadcast::Sender<SyncEvent>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl AutoSync {
+    /// Spawn the background sync of a client on the current runtime
+    ///
+    /// Panics if the interval is zero.
+    pub fn spawn(
+        client: ClientHandle,
+        network: Network,
+        interval: Duration,
+        policy: ConsumePolicy,
+    ) -> Self {
+        let policy = Arc::new(RwLock::new(policy));
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        let task = tokio::spawn(Self::run(
+            client,
+            network,
+            interval,
+            policy.clone(),
+            events.clone(),
+        ));
+
+        AutoSync {
+            policy,
+            events,
+            task,
+        }
+    }
+
+    /// Subscribe to the events emitted from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<SyncEvent> {
+        self.events.subscribe()
+    }
+
+    pub fn policy(&self) -> ConsumePolicy {
+        self.policy
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Replace the consume policy, applied from the next sync
+    pub fn set_policy(&self, policy: ConsumePolicy) {
+        *self
+            .policy
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = policy;
+    }
+
+    async fn run(
+        client: ClientHandle,
+        network: Network,
+        interval: Duration,
+        policy: Arc<RwLock<ConsumePolicy>>,
+        events: broadcast::Sender<SyncEvent>,
+    ) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut failed: HashSet<String> = HashSet::new();
+
+        loop {
+            ticker.tick().await;
+            if client.is_closed() {
+                break;
+            }
+
+            match client.sync().await {
+                Ok(summary) => {
+                    let _ = events.send(SyncEvent::Synced {
+                        block_num: summary.block_num.as_u32(),
+                        new_public_notes: summary.new_public_notes.len() as u32,
+                        committed_notes: summary.committed_notes.len() as u32,
+                        consumed_notes: summary.consumed_notes.len() as u32,
+                        updated_accounts: summary.updated_accounts.len() as u32,
+                    });
+                }
+                Err(error) => {
+                    tracing::warn!(error = %error, network = %network, "Background sync failed");
+                    let _ = events.send(SyncEvent::SyncFailed { error });
+                    continue;
+                }
+            }
+
+            let policy = policy
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone();
+            for account_id in policy.accounts() {
+                if let Some(event) =
+                    Self::consume_matching(&client, network, &policy, account_id, &mut failed).await
+                {
+                    let _ = events.send(event);
+                }
+            }
+        }
+    }
+
+    /// Consume the notes of the account matching the policy, if any
+    async fn consume_matching(
+        client: &ClientHandle,
+        network: Network,
+        policy: &ConsumePolicy,
+        account_id: AccountId,
+        failed: &mut HashSet<String>,
+    ) -> Option<SyncEvent> {
+        let account = Address::from(AccountIdAddress::new(
+            account_id,
+            AddressInterface::Unspecified,
+        ))
+        .to_bech32(network.to_network_id());
+
+        let notes = match client.list_consumable_notes(account_id, network).await {
+            Ok(notes) => notes,
+            Err(error) => {
+                tracing::warn!(error = %error, account_id = %account, "Failed to list consumable notes");
+                return None;
+            }
+        };
+
+        let note_ids: Vec<String> = notes
+            .into_iter()
+            .filter(|note| policy.matches(account_id, note) && !failed.contains(&note.note_id))
+            .map(|note| note.note_id)
+            .collect();
+        if note_ids.is_empty() {
+            return None;
+        }
+
+        let parsed = note_ids
+            .iter()
+            .map(|note_id| NoteId::try_from_hex(note_id))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Invalid note id: {}", e));
+        let result = match parsed {
+            Ok(parsed) => client.consume_notes(account_id, parsed).await,
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Ok(transaction_id) => {
+                tracing::info!(
+                    account_id = %account,
+                    transaction_id = %transaction_id,
+                    notes = note_ids.len(),
+                    "Automatically consumed notes"
+                );
+                Some(SyncEvent::NotesConsumed {
+                    account_id: account,
+                    note_ids,
+                    transaction_id,
+                })
+            }
+            Err(error) => {
+                tracing::warn!(error = %error, account_id = %account, "Failed to consume notes");
+                failed.extend(note_ids.iter().cloned());
+                Some(SyncEvent::ConsumeFailed {
+                    account_id: account,
+                    note_ids,
+                    error,
+                })
+            }
+        }
+    }
+}
+
+impl Drop for AutoSync {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miden_objects::testing::account_id::{
+        ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
+    };
+
+    fn note(
+        standard: Option<&str>,
+        script_root: Word,
+        consumable_after: Option<u32>,
+    ) -> ConsumableNoteData {
+        ConsumableNoteData {
+            note_id: "0x01".to_string(),
+            sender: None,
+            script_root: script_root.to_hex(),
+            standard: standard.map(str::to_string),
+            assets: vec![],
+            consumable_after,
+        }
+    }
+
+    #[test]
+    fn consume_policy_matches_rules_of_the_account() {
+        let wallet = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+        let desk = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE).unwrap();
+        let order_script = Word::from([1u32, 2, 3, 4]);
+        let policy = ConsumePolicy::default()
+            .with_rule(wallet, ConsumeRule::Payments)
+            .with_rule(desk, ConsumeRule::Scripts(vec![order_script]));
+
+        let payment = note(Some("P2IDE"), Word::default(), None);
+        let order = note(None, order_script, None);
+        assert!(policy.matches(wallet, &payment));
+        assert!(!policy.matches(wallet, &order));
+        assert!(policy.matches(desk, &order));
+        assert!(!policy.matches(desk, &payment));
+        assert!(!policy.matches(wallet, &note(Some("SWAP"), Word::default(), None)));
+
+        // Reclaimable notes are left until they can be consumed
+        assert!(!policy.matches(wallet, &note(Some("P2IDE"), Word::default(), Some(42))));
+        assert_eq!(policy.accounts().count(), 2);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{MidenTransactionId, Network, symbol::encode_symbol};
+use crate::{MidenTransactionId, Network, NetworkConfig, note::MidenNote, symbol::encode_symbol};
 use miden_client::{
     Client,
     account::{AccountHeader, AccountId, component::BasicWallet},
@@ -6,7 +6,8 @@ use miden_client::{
     builder::ClientBuilder,
     crypto::SecretKey,
     keystore::FilesystemKeyStore,
-    rpc::{Endpoint, TonicRpcClient},
+    note::{NoteConsumability, NoteId, NoteRelevance},
+    rpc::TonicRpcClient,
     store::{AccountRecord, AccountStatus},
     sync::SyncSummary,
     transaction::TransactionKernel,
@@ -15,6 +16,7 @@ use miden_lib::account::{
     auth::{AuthRpoFalcon512, NoAuth},
     faucets::BasicFungibleFaucet,
 };
+use miden_lib::note::well_known_note::WellKnownNote;
 use miden_objects::{
     Felt, Word,
     account::{
@@ -25,7 +27,7 @@ use miden_objects::{
     assembly::Assembler,
     asset::TokenSymbol,
 };
-use rand::{Rng, RngCore, rngs::StdRng};
+use rand::{RngCore, rngs::StdRng};
 use std::sync::Arc;
 use std::{
     env,
@@ -37,7 +39,7 @@ type DeskAccountArtifacts = (miden_client::account::Account, SecretKey, Option<S
 
 pub async fn create_client(
     path: &Path,
-    network: Network,
+    config: &NetworkConfig,
 ) -> Result<
     (
         Client<FilesystemKeyStore<StdRng>>,
@@ -45,13 +47,8 @@ pub async fn create_client(
     ),
     Box<dyn std::error::Error>,
 > {
-    let endpoint = match network {
-        Network::Testnet => Endpoint::testnet(),
-        Network::Localnet => Endpoint::localhost(),
-    };
-
-    let timeout_ms = 10_000;
-    let rpc_api = Arc::new(TonicRpcClient::new(&endpoint, timeout_ms));
+    let endpoint = config.endpoint()?;
+    let rpc_api = Arc::new(TonicRpcClient::new(&endpoint, config.timeout_ms));
     let keystore_path = path.join("keystore");
     let sqlite_path = path.join("miden.sqlite3");
     let keystore = Arc::new(FilesystemKeyStore::new(keystore_path)?);
@@ -67,6 +64,75 @@ pub async fn create_client(
     Ok((client, keystore))
 }
 
+/// Initial words of the desk settings slots, as encoded by `mosaic_fi::book`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeskSettings {
+    pub status: Word,
+    pub fees: Word,
+    pub limits: Word,
+    pub base_unit: Word,
+}
+
+/// Compile the desk book component with its storage layout
+///
+/// Slots 0-4 hold the desk configuration, followed by the sell side (5-9),
+/// the buy side (10-14), the fill log (15-16), the owner (17), the fees (18),
+/// the order limits (19), the base unit (20) and the script of the notes
+/// refunding backed offers (21), mirroring the offsets in `book.masm`.
+pub fn desk_book_component(
+    base_symbol_word: Word,
+    quote_symbol_word: Word,
+    owner_account: AccountId,
+    settings: DeskSettings,
+) -> Result<AccountComponent, String> {
+    let assembler: Assembler = TransactionKernel::assembler().with_debug_mode(true);
+    let book_code = include_str!("../../mosaic-fi/masm/accounts/book.masm").to_string();
+    let zero_word = || Word::from([Felt::new(0); 4]);
+
+    let component = AccountComponent::compile(
+        book_code,
+        assembler,
+        vec![
+            StorageSlot::Value(zero_word()),
+            StorageSlot::Value(base_symbol_word),
+            StorageSlot::Value(quote_symbol_word),
+            StorageSlot::Value(zero_word()),
+            StorageSlot::Value(settings.status),
+            // Sell
+            StorageSlot::Value(zero_word()),
+            StorageSlot::Map(StorageMap::new()),
+            StorageSlot::Value(zero_word()),
+            StorageSlot::Map(StorageMap::new()), // Quote requests
+            StorageSlot::Map(StorageMap::new()), // Intents
+            // Buy
+            StorageSlot::Value(zero_word()),
+            StorageSlot::Map(StorageMap::new()),
+            StorageSlot::Value(zero_word()),
+            StorageSlot::Map(StorageMap::new()), // Quote requests
+            StorageSlot::Map(StorageMap::new()), // Intents
+            // Fills
+            StorageSlot::Value(zero_word()),
+            StorageSlot::Map(StorageMap::new()),
+            // Owner
+            StorageSlot::Value(Word::from([
+                owner_account.suffix(),
+                owner_account.prefix().as_felt(),
+                Felt::new(0),
+                Felt::new(0),
+            ])),
+            StorageSlot::Value(settings.fees),
+            StorageSlot::Value(settings.limits),
+            StorageSlot::Value(settings.base_unit),
+            // Backed offers are refunded with P2ID notes
+            StorageSlot::Value(WellKnownNote::P2ID.script_root()),
+        ],
+    )
+    .map_err(|e| format!("Failed to compile desk component: {}", e))?
+    .with_supports_all_types();
+
+    Ok(component)
+}
+
 /// Commands that can be sent to the client thread
 pub enum ClientCommand {
     Sync {
@@ -87,6 +153,7 @@ pub enum ClientCommand {
         base_symbol: String,
         base_account: String,
         owner_account: AccountId,
+        settings: DeskSettings,
         respond_to: oneshot::Sender<Result<DeskAccountArtifacts, String>>,
     },
     GetAccount {
@@ -111,6 +178,30 @@ pub enum ClientCommand {
         network: Network,
         respond_to: oneshot::Sender<Result<crate::AccountStatusData, String>>,
     },
+    ListConsumableNotes {
+        account_id: AccountId,
+        network: Network,
+        respond_to: oneshot::Sender<Result<Vec<crate::ConsumableNoteData>, String>>,
+    },
+    ConsumeNotes {
+        account_id: AccountId,
+        note_ids: Vec<NoteId>,
+        respond_to: oneshot::Sender<Result<MidenTransactionId, String>>,
+    },
+    Mint {
+        faucet_id: AccountId,
+        target_id: AccountId,
+        amount: u64,
+        respond_to: oneshot::Sender<Result<(MidenTransactionId, MidenNote), String>>,
+    },
+    Transfer {
+        sender_id: AccountId,
+        target_id: AccountId,
+        faucet_id: AccountId,
+        amount: u64,
+        reclaim_after: Option<u32>,
+        respond_to: oneshot::Sender<Result<(MidenTransactionId, MidenNote), String>>,
+    },
     Shutdown,
 }
 
@@ -123,9 +214,11 @@ pub struct ClientHandle {
 
 impl ClientHandle {
     /// Create a new client handle and spawn a dedicated thread for the client
+    ///
+    /// The client connects to the node of the network configuration.
     pub async fn spawn(
         path: PathBuf,
-        network: Network,
+        config: NetworkConfig,
     ) -> Result<Self, Box<dyn std::error::Error>> {
         let (command_tx, command_rx) = mpsc::unbounded_channel();
         let (ready_tx, ready_rx) = oneshot::channel();
@@ -140,10 +233,10 @@ impl ClientHandle {
 
             // Initialize the client inside the dedicated thread
             rt.block_on(async move {
-                match create_client(path.as_path(), network).await {
+                match create_client(path.as_path(), &config).await {
                     Ok((client, keystore)) => {
                         let _ = ready_tx.send(Ok(keystore.clone()));
-                        Self::run_client_loop(client, command_rx).await;
+                        Self::run_client_loop(client, command_rx, config.endpoint).await;
                     }
                     Err(e) => {
                         let _ = ready_tx.send(Err(e.to_string()));
@@ -174,16 +267,17 @@ impl ClientHandle {
     async fn run_client_loop(
         mut client: Client<FilesystemKeyStore<StdRng>>,
         mut command_rx: mpsc::UnboundedReceiver<ClientCommand>,
+        endpoint: String,
     ) {
         while let Some(command) = command_rx.recv().await {
             match command {
                 ClientCommand::Sync { respond_to } => {
-                    let result = client
-                        .sync_state()
-                        .await
-                        .map_err(|e| {
-                            format!("Sync failed: {}. If using Localnet, ensure a Miden node is running on localhost:57291", e)
-                        });
+                    let result = client.sync_state().await.map_err(|e| {
+                        format!(
+                            "Sync failed: {}. Ensure a Miden node is reachable at {}",
+                            e, endpoint
+                        )
+                    });
 
                     let _ = respond_to.send(result);
                 }
@@ -212,6 +306,7 @@ impl ClientHandle {
                     base_symbol,
                     base_account,
                     owner_account,
+                    settings,
                     respond_to,
                 } => {
                     let result = Self::create_desk_account_impl(
@@ -221,6 +316,7 @@ impl ClientHandle {
                         &quote_symbol,
                         &quote_account,
                         owner_account,
+                        settings,
                     )
                     .await;
                     let _ = respond_to.send(result);
@@ -266,6 +362,51 @@ impl ClientHandle {
                     let result = Self::get_account_status_impl(&client, account_id, network).await;
                     let _ = respond_to.send(result);
                 }
+                ClientCommand::ListConsumableNotes {
+                    account_id,
+                    network,
+                    respond_to,
+                } => {
+                    let result =
+                        Self::list_consumable_notes_impl(&client, account_id, network).await;
+                    let _ = respond_to.send(result);
+                }
+                ClientCommand::ConsumeNotes {
+                    account_id,
+                    note_ids,
+                    respond_to,
+                } => {
+                    let result = Self::consume_notes_impl(&mut client, account_id, note_ids).await;
+                    let _ = respond_to.send(result);
+                }
+                ClientCommand::Mint {
+                    faucet_id,
+                    target_id,
+                    amount,
+                    respond_to,
+                } => {
+                    let result = Self::mint_impl(&mut client, faucet_id, target_id, amount).await;
+                    let _ = respond_to.send(result);
+                }
+                ClientCommand::Transfer {
+                    sender_id,
+                    target_id,
+                    faucet_id,
+                    amount,
+                    reclaim_after,
+                    respond_to,
+                } => {
+                    let result = Self::transfer_impl(
+                        &mut client,
+                        sender_id,
+                        target_id,
+                        faucet_id,
+                        amount,
+                        reclaim_after,
+                    )
+                    .await;
+                    let _ = respond_to.send(result);
+                }
                 ClientCommand::Shutdown => {
                     break;
                 }
@@ -347,8 +488,8 @@ impl ClientHandle {
         quote_symbol: &str,
         quote_account: &str,
         owner_account: AccountId,
+        settings: DeskSettings,
     ) -> Result<DeskAccountArtifacts, String> {
-        let _ = owner_account;
         if base_account == quote_account {
             return Err("Base and quote accounts must be different".to_string());
         }
@@ -389,11 +530,6 @@ impl ClientHandle {
         let key_pair = SecretKey::with_rng(client.rng());
         let mut init_seed = [0u8; 32];
         client.rng().fill_bytes(&mut init_seed);
-        let assembler: Assembler = TransactionKernel::assembler().with_debug_mode(true);
-
-        let book_code = include_str!("../../mosaic-fi/masm/accounts/book.masm").to_string();
-
-        let zero_word = || Word::from([Felt::new(0); 4]);
         let base_symbol_upper = base_symbol.to_ascii_uppercase();
         let quote_symbol_upper = quote_symbol.to_ascii_uppercase();
 
@@ -406,31 +542,8 @@ impl ClientHandle {
                 .map_err(|e| format!("Invalid quote symbol: {}", e))?,
         );
 
-        let book_component = AccountComponent::compile(
-            book_code,
-            assembler,
-            vec![
-                StorageSlot::Value(zero_word()),
-                StorageSlot::Value(base_symbol_word),
-                StorageSlot::Value(quote_symbol_word),
-                StorageSlot::Value(zero_word()),
-                StorageSlot::Value(zero_word()), // Status
-                // Sell
-                StorageSlot::Value(zero_word()),
-                StorageSlot::Map(StorageMap::new()),
-                StorageSlot::Value(zero_word()),
-                StorageSlot::Value(zero_word()),
-                StorageSlot::Value(zero_word()),
-                // Buy
-                StorageSlot::Value(zero_word()),
-                StorageSlot::Map(StorageMap::new()),
-                StorageSlot::Value(zero_word()),
-                StorageSlot::Value(zero_word()),
-                StorageSlot::Value(zero_word()),
-            ],
-        )
-        .map_err(|e| format!("Failed to compile desk component: {}", e))?
-        .with_supports_all_types();
+        let book_component =
+            desk_book_component(base_symbol_word, quote_symbol_word, owner_account, settings)?;
 
         let (book_contract, book_seed) = AccountBuilder::new(init_seed)
             .account_type(MidenAccountType::RegularAccountImmutableCode)
@@ -447,37 +560,6 @@ impl ClientHandle {
             .map_err(|e| format!("Failed to add desk account: {}", e))?;
         client.sync_state().await?;
 
-        // Workaround Start ===============================================================================================
-        let abstract_note = crate::note::MidenAbstractNote {
-            version: "MOSAIC 2025.10 MIDEN 0.11".to_string(),
-            note_type: crate::note::NoteType::Private,
-            program: include_str!("../../mosaic-fi/masm/notes/desk_update_status.masm").to_string(),
-            libraries: vec![(
-                "external_contract::book".to_string(),
-                include_str!("../../mosaic-fi/masm/accounts/book.masm").to_string(),
-            )],
-        };
-        let intent: [u64; 4] = [
-            client.rng().random(),
-            client.rng().random(),
-            client.rng().random(),
-            client.rng().random(),
-        ];
-        let inputs = vec![
-            ("intent".to_string(), crate::note::Value::Word(intent)),
-            ("status".to_string(), crate::note::Value::Word([1, 1, 1, 1])),
-        ];
-        let note = crate::note::compile_note(abstract_note, owner_account, zero_word(), inputs)
-            .map_err(|e| format!("Failed to compile note: {}", e))?;
-        let _ = crate::note::commit_note(client, owner_account, &note)
-            .await
-            .map_err(|e| format!("Failed to commit the note: {}", e))?;
-        client.sync_state().await?;
-        Self::consume_note_impl(client, book_contract.id(), &note.miden_note_hex)
-            .await
-            .map_err(|e| format!("Failed to consume note: {}", e))?;
-        // Workaround End ===============================================================================================
-
         let account_id = book_contract.id();
         let address = AccountIdAddress::new(account_id, AddressInterface::Unspecified);
         let network_id = network.to_network_id();
@@ -496,8 +578,6 @@ impl ClientHandle {
         account_id: AccountId,
         note_hex: &str,
     ) -> Result<MidenTransactionId, String> {
-        use crate::note::MidenNote;
-
         let miden_note = MidenNote {
             version: crate::version::VERSION_STRING.to_string(),
             note_type: crate::note::NoteType::Private,
@@ -626,14 +706,252 @@ impl ClientHandle {
         Ok(format!("{}", tx_id))
     }
 
+    /// Implementation of faucet minting logic
+    ///
+    /// The faucet mints the amount into a private P2ID note for the target,
+    /// the note is returned so that it can be handed to the target.
+    async fn mint_impl(
+        client: &mut Client<FilesystemKeyStore<StdRng>>,
+        faucet_id: AccountId,
+        target_id: AccountId,
+        amount: u64,
+    ) -> Result<(MidenTransactionId, MidenNote), String> {
+        use miden_client::transaction::TransactionRequestBuilder;
+        use miden_objects::asset::FungibleAsset;
+
+        if !faucet_id.is_faucet() {
+            return Err(format!("Account {} is not a faucet", faucet_id));
+        }
+
+        let asset = FungibleAsset::new(faucet_id, amount)
+            .map_err(|e| format!("Invalid mint amount: {}", e))?;
+        let tx_request = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(
+                asset,
+                target_id,
+                miden_client::note::NoteType::Private,
+                client.rng(),
+            )
+            .map_err(|e| format!("Failed to build mint transaction: {}", e))?;
+
+        let minted = Self::submit_note_request(client, faucet_id, tx_request).await?;
+        tracing::info!(
+            transaction_id = %minted.0,
+            faucet_id = %faucet_id,
+            target_id = %target_id,
+            amount,
+            "Minted assets"
+        );
+        Ok(minted)
+    }
+
+    /// Implementation of wallet transfer logic
+    ///
+    /// The sender pays the amount of the faucet asset into a private P2ID
+    /// note, or a P2IDE note the sender can reclaim once `reclaim_after`
+    /// blocks have passed.
+    async fn transfer_impl(
+        client: &mut Client<FilesystemKeyStore<StdRng>>,
+        sender_id: AccountId,
+        target_id: AccountId,
+        faucet_id: AccountId,
+        amount: u64,
+        reclaim_after: Option<u32>,
+    ) -> Result<(MidenTransactionId, MidenNote), String> {
+        use miden_client::transaction::{PaymentNoteDescription, TransactionRequestBuilder};
+        use miden_objects::asset::FungibleAsset;
+
+        let asset = FungibleAsset::new(faucet_id, amount)
+            .map_err(|e| format!("Invalid transfer amount: {}", e))?;
+        let mut payment = PaymentNoteDescription::new(vec![asset.into()], sender_id, target_id);
+        if let Some(blocks) = reclaim_after {
+            let height = client
+                .get_sync_height()
+                .await
+                .map_err(|e| format!("Failed to read sync height: {}", e))?;
+            payment = payment.with_reclaim_height(height + blocks);
+        }
+        let tx_request = TransactionRequestBuilder::new()
+            .build_pay_to_id(payment, miden_client::note::NoteType::Private, client.rng())
+            .map_err(|e| format!("Failed to build transfer transaction: {}", e))?;
+
+        let transferred = Self::submit_note_request(client, sender_id, tx_request).await?;
+        tracing::info!(
+            transaction_id = %transferred.0,
+            sender_id = %sender_id,
+            target_id = %target_id,
+            faucet_id = %faucet_id,
+            amount,
+            ?reclaim_after,
+            "Transferred assets"
+        );
+        Ok(transferred)
+    }
+
+    /// Execute and submit a request creating a single note, returning the note
+    async fn submit_note_request(
+        client: &mut Client<FilesystemKeyStore<StdRng>>,
+        account_id: AccountId,
+        tx_request: miden_client::transaction::TransactionRequest,
+    ) -> Result<(MidenTransactionId, MidenNote), String> {
+        use miden_lib::utils::Serializable;
+
+        let note = tx_request
+            .expected_output_own_notes()
+            .into_iter()
+            .next()
+            .ok_or_else(|| "Transaction creates no note".to_string())?;
+
+        let tx_result = client
+            .new_transaction(account_id, tx_request)
+            .await
+            .map_err(|e| format!("Failed to execute transaction: {:?}", e))?;
+        let tx_id = tx_result.executed_transaction().id();
+        client
+            .submit_transaction(tx_result)
+            .await
+            .map_err(|e| format!("Failed to submit transaction: {}", e))?;
+
+        let miden_note = MidenNote {
+            version: crate::version::VERSION_STRING.to_string(),
+            note_type: crate::note::NoteType::Private,
+            miden_note_hex: hex::encode(note.to_bytes()),
+        };
+        Ok((format!("{}", tx_id), miden_note))
+    }
+
+    /// Describe an asset, with its faucet in bech32 format
+    fn asset_data(
+        asset: &miden_objects::asset::Asset,
+        network_id: miden_objects::account::NetworkId,
+    ) -> crate::AssetData {
+        use miden_objects::asset::Asset;
+
+        match asset {
+            Asset::Fungible(fungible_asset) => {
+                let faucet_address = AccountIdAddress::new(
+                    fungible_asset.faucet_id(),
+                    AddressInterface::Unspecified,
+                );
+                crate::AssetData {
+                    faucet: Address::from(faucet_address).to_bech32(network_id),
+                    amount: fungible_asset.amount(),
+                    fungible: true,
+                }
+            }
+            // For non-fungible assets, we'll use a placeholder
+            // In a production system, you'd want to properly handle this
+            Asset::NonFungible(_non_fungible_asset) => crate::AssetData {
+                faucet: "non-fungible".to_string(),
+                amount: 1,
+                fungible: false,
+            },
+        }
+    }
+
+    /// Implementation of consumable note listing
+    ///
+    /// Lists the committed input notes discovered by sync that the account
+    /// can consume, now or after some block.
+    async fn list_consumable_notes_impl(
+        client: &Client<FilesystemKeyStore<StdRng>>,
+        account_id: AccountId,
+        network: Network,
+    ) -> Result<Vec<crate::ConsumableNoteData>, String> {
+        let network_id = network.to_network_id();
+        let notes = client
+            .get_consumable_notes(Some(account_id))
+            .await
+            .map_err(|e| format!("Failed to get consumable notes: {}", e))?;
+
+        Ok(notes
+            .into_iter()
+            .filter_map(|(record, consumability)| {
+                let relevance = consumability
+                    .into_iter()
+                    .find(|(consumer, _)| *consumer == account_id)?
+                    .1;
+                let sender = record.metadata().map(|metadata| {
+                    let address =
+                        AccountIdAddress::new(metadata.sender(), AddressInterface::Unspecified);
+                    Address::from(address).to_bech32(network_id)
+                });
+                let script_root = record.details().script().root();
+                let standard = [
+                    (WellKnownNote::P2ID, "P2ID"),
+                    (WellKnownNote::P2IDE, "P2IDE"),
+                    (WellKnownNote::SWAP, "SWAP"),
+                ]
+                .into_iter()
+                .find(|(note, _)| note.script_root() == script_root)
+                .map(|(_, name)| name.to_string());
+                Some(crate::ConsumableNoteData {
+                    note_id: record.id().to_hex(),
+                    sender,
+                    script_root: script_root.to_hex(),
+                    standard,
+                    assets: record
+                        .assets()
+                        .iter()
+                        .map(|asset| Self::asset_data(asset, network_id))
+                        .collect(),
+                    consumable_after: match relevance {
+                        NoteRelevance::Now => None,
+                        NoteRelevance::After(block) => Some(block),
+                    },
+                })
+            })
+            .collect())
+    }
+
+    /// Implementation of consumption of input notes by id
+    ///
+    /// The notes must have been discovered by sync and be consumable by the
+    /// account now, they are consumed in a single transaction.
+    async fn consume_notes_impl(
+        client: &mut Client<FilesystemKeyStore<StdRng>>,
+        account_id: AccountId,
+        note_ids: Vec<NoteId>,
+    ) -> Result<MidenTransactionId, String> {
+        use miden_client::transaction::TransactionRequestBuilder;
+
+        let consumable = client
+            .get_consumable_notes(Some(account_id))
+            .await
+            .map_err(|e| format!("Failed to get consumable notes: {}", e))?
+            .into_iter()
+            .map(|(record, consumability)| (record.id(), consumability))
+            .collect::<Vec<_>>();
+        check_consumable_now(account_id, &note_ids, &consumable)?;
+
+        let tx_request = TransactionRequestBuilder::new()
+            .build_consume_notes(note_ids.clone())
+            .map_err(|e| format!("Failed to build transaction: {:?}", e))?;
+        let tx_result = client
+            .new_transaction(account_id, tx_request)
+            .await
+            .map_err(|e| format!("Failed to execute transaction: {:?}", e))?;
+        let tx_id = tx_result.executed_transaction().id();
+        client
+            .submit_transaction(tx_result)
+            .await
+            .map_err(|e| format!("Failed to submit transaction: {}", e))?;
+
+        tracing::info!(
+            transaction_id = %tx_id,
+            account_id = %account_id,
+            notes = note_ids.len(),
+            "Consumed input notes"
+        );
+        Ok(format!("{}", tx_id))
+    }
+
     /// Implementation of getting account status
     async fn get_account_status_impl(
         client: &Client<FilesystemKeyStore<StdRng>>,
         account_id: AccountId,
         network: Network,
     ) -> Result<crate::AccountStatusData, String> {
-        use miden_objects::asset::Asset;
-
         // Get account
         let account_record = client
             .get_account(account_id)
@@ -653,37 +971,11 @@ impl ClientHandle {
         // Get network ID for bech32 encoding
         let network_id = network.to_network_id();
 
-        // Iterate through assets
-        let mut assets = Vec::new();
-        for asset in account.vault().assets() {
-            match asset {
-                Asset::Fungible(fungible_asset) => {
-                    let faucet_id = fungible_asset.faucet_id();
-                    // Convert to bech32 format
-                    let faucet_address = miden_objects::address::AccountIdAddress::new(
-                        faucet_id,
-                        miden_objects::address::AddressInterface::Unspecified,
-                    );
-                    let faucet_bech32 =
-                        miden_objects::address::Address::from(faucet_address).to_bech32(network_id);
-
-                    assets.push(crate::AssetData {
-                        faucet: faucet_bech32,
-                        amount: fungible_asset.amount(),
-                        fungible: true,
-                    });
-                }
-                Asset::NonFungible(_non_fungible_asset) => {
-                    // For non-fungible assets, we'll use a placeholder
-                    // In a production system, you'd want to properly handle this
-                    assets.push(crate::AssetData {
-                        faucet: "non-fungible".to_string(),
-                        amount: 1,
-                        fungible: false,
-                    });
-                }
-            }
-        }
+        let assets = account
+            .vault()
+            .assets()
+            .map(|asset| Self::asset_data(&asset, network_id))
+            .collect();
 
         // Get account ID in bech32 format
         let account_address = miden_objects::address::AccountIdAddress::new(
@@ -766,7 +1058,8 @@ impl ClientHandle {
         Ok(account)
     }
 
-    /// Create a new desk account in the client
+    /// Create a new desk account in the client, owned by the given account
+    /// and starting with the given settings
     /// Returns the account (the secret key is automatically stored in the keystore)
     pub async fn create_desk_account(
         &self,
@@ -775,6 +1068,7 @@ impl ClientHandle {
         quote_symbol: String,
         quote_account: String,
         owner_account: AccountId,
+        settings: DeskSettings,
     ) -> Result<(miden_client::account::Account, Option<String>), String> {
         let (respond_to, response_rx) = oneshot::channel();
 
@@ -785,6 +1079,7 @@ impl ClientHandle {
                 base_symbol,
                 base_account,
                 owner_account,
+                settings,
                 respond_to,
             })
             .map_err(|_| "Client thread has shut down".to_string())?;
@@ -896,8 +1191,184 @@ impl ClientHandle {
             .map_err(|_| "Client thread dropped response".to_string())?
     }
 
+    /// List the input notes the account can consume
+    pub async fn list_consumable_notes(
+        &self,
+        account_id: AccountId,
+        network: Network,
+    ) -> Result<Vec<crate::ConsumableNoteData>, String> {
+        let (respond_to, response_rx) = oneshot::channel();
+
+        self.command_tx
+            .send(ClientCommand::ListConsumableNotes {
+                account_id,
+                network,
+                respond_to,
+            })
+            .map_err(|_| "Client thread has shut down".to_string())?;
+
+        response_rx
+            .await
+            .map_err(|_| "Client thread dropped response".to_string())?
+    }
+
+    /// Consume input notes discovered by sync, by note id
+    /// Returns the transaction ID
+    pub async fn consume_notes(
+        &self,
+        account_id: AccountId,
+        note_ids: Vec<NoteId>,
+    ) -> Result<MidenTransactionId, String> {
+        let (respond_to, response_rx) = oneshot::channel();
+
+        self.command_tx
+            .send(ClientCommand::ConsumeNotes {
+                account_id,
+                note_ids,
+                respond_to,
+            })
+            .map_err(|_| "Client thread has shut down".to_string())?;
+
+        response_rx
+            .await
+            .map_err(|_| "Client thread dropped response".to_string())?
+    }
+
+    /// Mint an amount of a faucet into a P2ID note for the target account
+    ///
+    /// Returns the transaction ID and the private note to hand to the target
+    pub async fn mint(
+        &self,
+        faucet_id: AccountId,
+        target_id: AccountId,
+        amount: u64,
+    ) -> Result<(MidenTransactionId, MidenNote), String> {
+        let (respond_to, response_rx) = oneshot::channel();
+
+        self.command_tx
+            .send(ClientCommand::Mint {
+                faucet_id,
+                target_id,
+                amount,
+                respond_to,
+            })
+            .map_err(|_| "Client thread has shut down".to_string())?;
+
+        response_rx
+            .await
+            .map_err(|_| "Client thread dropped response".to_string())?
+    }
+
+    /// Transfer an amount of a faucet asset from a wallet to the target account
+    ///
+    /// With `reclaim_after`, the sender can reclaim the note once that many
+    /// blocks have passed. Returns the transaction ID and the private note to
+    /// hand to the target.
+    pub async fn transfer(
+        &self,
+        sender_id: AccountId,
+        target_id: AccountId,
+        faucet_id: AccountId,
+        amount: u64,
+        reclaim_after: Option<u32>,
+    ) -> Result<(MidenTransactionId, MidenNote), String> {
+        let (respond_to, response_rx) = oneshot::channel();
+
+        self.command_tx
+            .send(ClientCommand::Transfer {
+                sender_id,
+                target_id,
+                faucet_id,
+                amount,
+                reclaim_after,
+                respond_to,
+            })
+            .map_err(|_| "Client thread has shut down".to_string())?;
+
+        response_rx
+            .await
+            .map_err(|_| "Client thread dropped response".to_string())?
+    }
+
+    /// Whether the client thread has shut down
+    pub fn is_closed(&self) -> bool {
+        self.command_tx.is_closed()
+    }
+
     /// Shutdown the client thread gracefully
     pub fn shutdown(&self) {
         let _ = self.command_tx.send(ClientCommand::Shutdown);
     }
 }
+
+/// Check that the notes can be consumed by the account now
+///
+/// The consumable notes are listed with the accounts able to consume them.
+fn check_consumable_now(
+    account_id: AccountId,
+    note_ids: &[NoteId],
+    consumable: &[(NoteId, Vec<NoteConsumability>)],
+) -> Result<(), String> {
+    if note_ids.is_empty() {
+        return Err("No notes to consume".to_string());
+    }
+
+    for note_id in note_ids {
+        let ready = consumable.iter().any(|(id, consumability)| {
+            id == note_id
+                && consumability.iter().any(|(consumer, relevance)| {
+                    *consumer == account_id && *relevance == NoteRelevance::Now
+                })
+        });
+        if !ready {
+            return Err(format!(
+                "Note {} is not consumable by {}",
+                note_id.to_hex(),
+                account_id
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miden_objects::testing::account_id::{
+        ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
+    };
+
+    #[test]
+    fn only_notes_consumable_now_are_consumed() {
+        let wallet = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+        let other = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE).unwrap();
+        let payment = NoteId::from(Word::from([1u32, 0, 0, 0]));
+        let reclaimable = NoteId::from(Word::from([2u32, 0, 0, 0]));
+        let unknown = NoteId::from(Word::from([3u32, 0, 0, 0]));
+        let consumable = vec![
+            (
+                payment,
+                vec![
+                    (wallet, NoteRelevance::Now),
+                    (other, NoteRelevance::After(5)),
+                ],
+            ),
+            (reclaimable, vec![(wallet, NoteRelevance::After(10))]),
+        ];
+
+        check_consumable_now(wallet, &[payment], &consumable).unwrap();
+        assert_eq!(
+            check_consumable_now(wallet, &[], &consumable).unwrap_err(),
+            "No notes to consume"
+        );
+
+        // Notes consumable later, by another account or not discovered are refused
+        for (account_id, note_id) in [(wallet, reclaimable), (other, payment), (wallet, unknown)] {
+            let err =
+                check_consumable_now(account_id, &[payment, note_id], &consumable).unwrap_err();
+            assert!(err.contains("is not consumable by"), "{err}");
+            assert!(err.contains(&note_id.to_hex()), "{err}");
+        }
+    }
+}
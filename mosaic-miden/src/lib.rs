@@ -1,4 +1,5 @@
 pub mod account;
+pub mod auto_sync;
 pub mod client;
 pub mod note;
 pub mod store;
@@ -8,41 +9,148 @@ pub mod version;
 
 pub type MidenTransactionId = String;
 
+use miden_client::rpc::Endpoint;
 use miden_objects::account::NetworkId;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
+use std::sync::OnceLock;
+
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+
+static NETWORK_CONFIGS: OnceLock<HashMap<Network, NetworkConfig>> = OnceLock::new();
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub enum Network {
     Testnet,
+    Devnet,
+    Mainnet,
     Localnet,
 }
 
+/// Node connection of a network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct NetworkConfig {
+    /// RPC endpoint of the node, such as "https://rpc.testnet.miden.io"
+    pub endpoint: String,
+    /// RPC timeout in milliseconds
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Bech32 prefix of the account addresses, such as "mtst"
+    pub network_id: String,
+}
+
+fn default_timeout_ms() -> u64 {
+    DEFAULT_TIMEOUT_MS
+}
+
+impl NetworkConfig {
+    pub fn endpoint(&self) -> Result<Endpoint, String> {
+        Endpoint::try_from(self.endpoint.as_str())
+            .map_err(|e| format!("Invalid endpoint '{}': {}", self.endpoint, e))
+    }
+
+    pub fn network_id(&self) -> Result<NetworkId, String> {
+        NetworkId::new(&self.network_id)
+            .map_err(|e| format!("Invalid network id '{}': {}", self.network_id, e))
+    }
+
+    fn check(&self) -> Result<(), String> {
+        self.endpoint()?;
+        self.network_id()?;
+        Ok(())
+    }
+}
+
+/// Install the node configuration of the networks, before any client is spawned
+///
+/// Networks left out keep their default configuration. The configuration can
+/// only be installed once per process.
+pub fn configure_networks(configs: HashMap<Network, NetworkConfig>) -> Result<(), String> {
+    let mut network_ids = HashMap::new();
+    for network in Network::ALL {
+        let network_id = match configs.get(&network) {
+            Some(config) => {
+                config
+                    .check()
+                    .map_err(|e| format!("Invalid {} configuration: {}", network, e))?;
+                config.network_id()?
+            }
+            None => network.default_network_id(),
+        };
+        if let Some(other) = network_ids.insert(network_id.to_string(), network) {
+            return Err(format!(
+                "{} and {} share the network id '{}'",
+                other, network, network_id
+            ));
+        }
+    }
+
+    NETWORK_CONFIGS
+        .set(configs)
+        .map_err(|_| "Networks are already configured".to_string())
+}
+
 impl Network {
-    pub fn to_network_id(&self) -> NetworkId {
-        match self {
-            Network::Testnet => NetworkId::Testnet,
-            // Network ID used for local instances of the node
-            Network::Localnet => NetworkId::new("mlcl").expect("mlcl should be a valid network ID"),
+    pub const ALL: [Network; 4] = [
+        Network::Testnet,
+        Network::Devnet,
+        Network::Mainnet,
+        Network::Localnet,
+    ];
+
+    /// Node configuration of the network, the installed one or its default
+    pub fn config(&self) -> Result<NetworkConfig, String> {
+        if let Some(config) = NETWORK_CONFIGS.get().and_then(|configs| configs.get(self)) {
+            return Ok(config.clone());
         }
+
+        let endpoint = match self {
+            Network::Testnet => Endpoint::testnet(),
+            Network::Devnet => Endpoint::devnet(),
+            Network::Localnet => Endpoint::localhost(),
+            Network::Mainnet => {
+                return Err(format!("No endpoint configured for {}", self));
+            }
+        };
+        Ok(NetworkConfig {
+            endpoint: endpoint.to_string(),
+            timeout_ms: DEFAULT_TIMEOUT_MS,
+            network_id: self.default_network_id().to_string(),
+        })
+    }
+
+    pub fn to_network_id(&self) -> NetworkId {
+        NETWORK_CONFIGS
+            .get()
+            .and_then(|configs| configs.get(self))
+            // Configurations are checked when installed
+            .and_then(|config| config.network_id().ok())
+            .unwrap_or_else(|| self.default_network_id())
     }
 
     pub fn from_network_id(network_id: NetworkId) -> Option<Self> {
-        if network_id == NetworkId::Testnet {
-            return Some(Network::Testnet);
-        }
+        Network::ALL
+            .into_iter()
+            .find(|network| network.to_network_id() == network_id)
+    }
 
-        if network_id == Network::Localnet.to_network_id() {
-            return Some(Network::Localnet);
+    fn default_network_id(&self) -> NetworkId {
+        match self {
+            Network::Testnet => NetworkId::Testnet,
+            Network::Devnet => NetworkId::Devnet,
+            Network::Mainnet => NetworkId::Mainnet,
+            // Network ID used for local instances of the node
+            Network::Localnet => NetworkId::new("mlcl").expect("mlcl should be a valid network ID"),
         }
-
-        None
     }
 
     pub fn as_str(&self) -> &'static str {
         match self {
             Network::Testnet => "Testnet",
+            Network::Devnet => "Devnet",
+            Network::Mainnet => "Mainnet",
             Network::Localnet => "Localnet",
         }
     }
@@ -60,9 +168,11 @@ impl std::str::FromStr for Network {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_ascii_lowercase().as_str() {
             "testnet" => Ok(Network::Testnet),
+            "devnet" => Ok(Network::Devnet),
+            "mainnet" => Ok(Network::Mainnet),
             "localnet" => Ok(Network::Localnet),
             _ => Err(format!(
-                "Unsupported network '{s}'. Expected 'Testnet' or 'Localnet'."
+                "Unsupported network '{s}'. Expected 'Testnet', 'Devnet', 'Mainnet' or 'Localnet'."
             )),
         }
     }
@@ -119,3 +229,73 @@ pub struct AccountStatusData {
     /// List of assets held by the account
     pub assets: Vec<AssetData>,
 }
+
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct ConsumableNoteData {
+    /// Note ID in hex format
+    pub note_id: String,
+    /// Sender account ID in bech32 format, when the note metadata is known
+    pub sender: Option<String>,
+    /// Root of the note script in hex format
+    pub script_root: String,
+    /// Standard note of the script: "P2ID", "P2IDE" or "SWAP"
+    pub standard: Option<String>,
+    /// Assets carried by the note
+    pub assets: Vec<AssetData>,
+    /// Block after which the note can be consumed, None when it can be now
+    pub consumable_after: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_defaults() {
+        for network in Network::ALL {
+            assert_eq!(
+                Network::from_network_id(network.to_network_id()),
+                Some(network)
+            );
+            assert_eq!(network.as_str().parse::<Network>().unwrap(), network);
+        }
+        assert_eq!(Network::Devnet.to_network_id(), NetworkId::Devnet);
+
+        let testnet = Network::Testnet.config().unwrap();
+        assert_eq!(testnet.endpoint, "https://rpc.testnet.miden.io");
+        assert_eq!(testnet.timeout_ms, DEFAULT_TIMEOUT_MS);
+        assert_eq!(testnet.network_id, "mtst");
+
+        // Mainnet has no default node
+        assert!(Network::Mainnet.config().is_err());
+    }
+
+    #[test]
+    fn test_network_config() {
+        let config: NetworkConfig = serde_json::from_str(
+            r#"{"endpoint": "http://node.internal:57291", "network_id": "mcst"}"#,
+        )
+        .unwrap();
+        assert_eq!(config.timeout_ms, DEFAULT_TIMEOUT_MS);
+        assert_eq!(config.endpoint().unwrap().port(), Some(57291));
+
+        // Each network needs its own address prefix
+        let shared = HashMap::from([(
+            Network::Localnet,
+            NetworkConfig {
+                network_id: "mtst".to_string(),
+                ..config.clone()
+            },
+        )]);
+        assert!(configure_networks(shared).is_err());
+
+        let invalid = HashMap::from([(
+            Network::Mainnet,
+            NetworkConfig {
+                endpoint: "http://node.internal:port".to_string(),
+                ..config
+            },
+        )]);
+        assert!(configure_networks(invalid).is_err());
+    }
+}
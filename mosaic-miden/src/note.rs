@@ -12,12 +12,16 @@ use miden_client::{
     transaction::{OutputNote, TransactionKernel, TransactionRequestBuilder},
 };
 use miden_lib::{
-    note::create_p2id_note,
+    note::{create_p2id_note, utils::build_p2id_recipient},
     utils::{Deserializable, Serializable},
 };
 use miden_objects::{
-    Felt, Word, asset::FungibleAsset, crypto::rand::RpoRandomCoin, note::NoteScript,
-    note::NoteType as MidenNoteType, transaction::TransactionScript,
+    Felt, Word,
+    asset::{Asset, FungibleAsset},
+    crypto::rand::RpoRandomCoin,
+    note::NoteScript,
+    note::NoteType as MidenNoteType,
+    transaction::TransactionScript,
 };
 use rand::rngs::StdRng;
 use serde::{Deserialize, Serialize};
@@ -199,6 +203,25 @@ pub fn compile_note(
     account_id: AccountId,
     secret: Word,
     inputs: Inputs,
+) -> Result<MidenNote, Box<dyn std::error::Error>> {
+    compile_note_with_assets(note, account_id, secret, inputs, vec![], broadcast_tag())
+}
+
+/// Tag of notes that are not addressed to a specific account
+pub fn broadcast_tag() -> NoteTag {
+    NoteTag::for_local_use_case(0, 0).unwrap()
+}
+
+/// Compile an abstract note carrying assets, sent with the given tag
+///
+/// The assets are moved out of the vault of the account committing the note.
+pub fn compile_note_with_assets(
+    note: MidenAbstractNote,
+    account_id: AccountId,
+    secret: Word,
+    inputs: Inputs,
+    assets: Vec<Asset>,
+    tag: NoteTag,
 ) -> Result<MidenNote, Box<dyn std::error::Error>> {
     let note_script = build_note_script(&note).unwrap();
     let mut inputs_inner: Vec<Felt> = vec![];
@@ -214,7 +237,6 @@ pub fn compile_note(
     }
     let note_inputs = NoteInputs::new(inputs_inner)?;
     let note_recipient = NoteRecipient::new(secret, note_script, note_inputs);
-    let tag = NoteTag::for_local_use_case(0, 0).unwrap();
     let metadata = NoteMetadata::new(
         account_id,
         miden_client::note::NoteType::Private,
@@ -222,7 +244,7 @@ pub fn compile_note(
         NoteExecutionHint::always(),
         Felt::new(0),
     )?;
-    let assets = NoteAssets::new(vec![])?;
+    let assets = NoteAssets::new(assets)?;
     let note_inner = Note::new(assets, metadata, note_recipient);
 
     let mut buffer = Vec::new();
@@ -237,6 +259,12 @@ pub fn compile_note(
     Ok(miden_note)
 }
 
+/// Decode the serialized Miden note carried by a `MidenNote`
+pub fn decode_note(note: &MidenNote) -> Result<Note, Box<dyn std::error::Error>> {
+    let note_bytes = hex::decode(&note.miden_note_hex)?;
+    Ok(Note::read_from_bytes(&note_bytes)?)
+}
+
 /// Compile a P2ID (Pay-to-ID) note for transferring fungible assets
 ///
 /// This function creates a note that transfers fungible assets from a faucet account
@@ -283,6 +311,46 @@ pub fn compile_p2id_note(
     })
 }
 
+/// Recipient digest of a P2ID note paying the target account
+///
+/// Used by notes that create a payback note when consumed.
+pub fn p2id_recipient_digest(
+    target_account_id: AccountId,
+    serial_num: Word,
+) -> Result<Word, Box<dyn std::error::Error>> {
+    Ok(build_p2id_recipient(target_account_id, serial_num)?.digest())
+}
+
+/// Compile the private P2ID payback note created when consuming a note
+///
+/// The note is rebuilt from its serial number so the target account can
+/// consume it without having seen its details.
+pub fn compile_payback_note(
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    asset: Asset,
+    serial_num: Word,
+) -> Result<MidenNote, Box<dyn std::error::Error>> {
+    let recipient = build_p2id_recipient(target_account_id, serial_num)?;
+    let metadata = NoteMetadata::new(
+        sender_account_id,
+        MidenNoteType::Private,
+        NoteTag::from_account_id(target_account_id),
+        NoteExecutionHint::always(),
+        Felt::new(0),
+    )?;
+    let payback_note = Note::new(NoteAssets::new(vec![asset])?, metadata, recipient);
+
+    let mut buffer = Vec::new();
+    payback_note.write_into(&mut buffer);
+
+    Ok(MidenNote {
+        version: version::VERSION_STRING.to_string(),
+        note_type: NoteType::Private,
+        miden_note_hex: hex::encode(&buffer),
+    })
+}
+
 pub async fn commit_note(
     client: &mut Client<FilesystemKeyStore<StdRng>>,
     account_id: AccountId,
@@ -57,10 +57,7 @@ impl Store {
         account_type: &str,
         name: Option<&str>,
     ) -> SqliteResult<()> {
-        let network_str = match network {
-            Network::Testnet => "Testnet",
-            Network::Localnet => "Localnet",
-        };
+        let network_str = network.as_str();
 
         self.conn.execute(
             "INSERT OR REPLACE INTO accounts (id, network, typ, name) VALUES (?1, ?2, ?3, ?4)",
@@ -95,10 +92,7 @@ impl Store {
         &self,
         network: Network,
     ) -> SqliteResult<Vec<AccountByNetworkRow>> {
-        let network_str = match network {
-            Network::Testnet => "Testnet",
-            Network::Localnet => "Localnet",
-        };
+        let network_str = network.as_str();
 
         let mut stmt = self
             .conn
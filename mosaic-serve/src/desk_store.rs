@@ -1,4 +1,8 @@
-use mosaic_fi::{Market, note::MosaicNote};
+use mosaic_fi::{
+    Market,
+    book::{DeskStatus, Fill},
+    note::MosaicNote,
+};
 use mosaic_miden::Network;
 use rusqlite::{Connection, Result as SqliteResult, params};
 use std::{
@@ -16,6 +20,27 @@ pub enum NoteStatus {
 
 pub type DeskNoteRecord = (i64, MosaicNote, NoteStatus);
 
+/// Response emitted by a desk for a note it consumed, at most one of each kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseKind {
+    /// Offer or decline answering a quote request
+    Quote,
+    /// Settlement of an offered quote request
+    Settlement,
+    /// Locked or not locked acknowledgement of a limit order
+    LimitOrderAck,
+}
+
+impl AsRef<str> for ResponseKind {
+    fn as_ref(&self) -> &str {
+        match self {
+            ResponseKind::Quote => "quote",
+            ResponseKind::Settlement => "settlement",
+            ResponseKind::LimitOrderAck => "limit_order_ack",
+        }
+    }
+}
+
 impl AsRef<str> for NoteStatus {
     fn as_ref(&self) -> &str {
         match self {
@@ -81,13 +106,15 @@ impl DeskStore {
                 base_issuer TEXT NOT NULL,
                 quote_code TEXT NOT NULL,
                 quote_issuer TEXT NOT NULL,
-                market_url TEXT
+                market_url TEXT,
+                status INTEGER
             )",
             [],
         )?;
 
         let _ = conn.execute("ALTER TABLE desks ADD COLUMN owner_account TEXT", []);
         let _ = conn.execute("ALTER TABLE desks ADD COLUMN market_url TEXT", []);
+        let _ = conn.execute("ALTER TABLE desks ADD COLUMN status INTEGER", []);
 
         Ok(())
     }
@@ -101,6 +128,7 @@ impl DeskStore {
     }
 
     /// Insert a new desk record
+    #[allow(clippy::too_many_arguments)]
     pub fn insert_desk(
         &self,
         desk_account: &str,
@@ -111,10 +139,7 @@ impl DeskStore {
         market: &Market,
         market_url: &str,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let network_str = match network {
-            Network::Testnet => "Testnet",
-            Network::Localnet => "Localnet",
-        };
+        let network_str = network.as_str();
 
         let path_str = path
             .to_str()
@@ -167,6 +192,8 @@ impl DeskStore {
 
             let network = match network_str.as_str() {
                 "Testnet" => Network::Testnet,
+                "Devnet" => Network::Devnet,
+                "Mainnet" => Network::Mainnet,
                 "Localnet" => Network::Localnet,
                 _ => {
                     return Err(rusqlite::Error::FromSqlConversionFailure(
@@ -210,6 +237,43 @@ impl DeskStore {
         Ok(desks)
     }
 
+    /// Record the last known trading status of a desk
+    pub fn update_desk_status(
+        &self,
+        desk_account: &str,
+        status: DeskStatus,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let updated = self.conn.execute(
+            "UPDATE desks SET status = ?1 WHERE desk_account = ?2",
+            params![status.code() as i64, desk_account],
+        )?;
+        if updated == 0 {
+            return Err(format!("Desk not found: {}", desk_account).into());
+        }
+        Ok(())
+    }
+
+    /// Get the last known trading status of a desk, if any was recorded
+    pub fn get_desk_status(
+        &self,
+        desk_account: &str,
+    ) -> Result<Option<DeskStatus>, Box<dyn std::error::Error>> {
+        let code: Option<i64> = match self.conn.query_row(
+            "SELECT status FROM desks WHERE desk_account = ?1",
+            params![desk_account],
+            |row| row.get(0),
+        ) {
+            Ok(code) => code,
+            Err(rusqlite::Error::QueryReturnedNoRows) => {
+                return Err(format!("Desk not found: {}", desk_account).into());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        code.map(|code| DeskStatus::from_code(code as u64))
+            .transpose()
+    }
+
     /// Delete a desk by account identifier
     pub fn delete_desk(&self, desk_account: &str) -> Result<(), Box<dyn std::error::Error>> {
         self.conn.execute(
@@ -240,6 +304,8 @@ impl DeskStore {
 
             let network = match network_str.as_str() {
                 "Testnet" => Network::Testnet,
+                "Devnet" => Network::Devnet,
+                "Mainnet" => Network::Mainnet,
                 "Localnet" => Network::Localnet,
                 _ => {
                     return Err(rusqlite::Error::FromSqlConversionFailure(
@@ -303,6 +369,8 @@ impl DeskStore {
 
             let network = match network_str.as_str() {
                 "Testnet" => Network::Testnet,
+                "Devnet" => Network::Devnet,
+                "Mainnet" => Network::Mainnet,
                 "Localnet" => Network::Localnet,
                 _ => {
                     return Err(rusqlite::Error::FromSqlConversionFailure(
@@ -370,6 +438,29 @@ impl DeskNoteStore {
             [],
         )?;
 
+        // Fills executed by the desk, keyed by their on-chain sequence number
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS fills (
+                id INTEGER PRIMARY KEY,
+                note_id INTEGER NOT NULL,
+                fill_json TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Responses to consumed notes, so that a note is answered once
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS responses (
+                note_id INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                note_json TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                PRIMARY KEY (note_id, kind)
+            )",
+            [],
+        )?;
+
         Ok(DeskNoteStore { conn })
     }
 
@@ -502,4 +593,121 @@ impl DeskNoteStore {
             Err(e) => Err(Box::new(e)),
         }
     }
+
+    /// Record a fill produced while consuming a note
+    pub fn insert_fill(&self, note_id: i64, fill: &Fill) -> Result<(), Box<dyn std::error::Error>> {
+        let fill_json = serde_json::to_string(fill)?;
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_secs() as i64;
+
+        self.conn.execute(
+            "INSERT OR REPLACE INTO fills (id, note_id, fill_json, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![fill.id as i64, note_id, fill_json, now],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get the id of the last recorded fill, zero when none
+    pub fn last_fill_id(&self) -> Result<u64, Box<dyn std::error::Error>> {
+        let last: Option<i64> = self
+            .conn
+            .query_row("SELECT MAX(id) FROM fills", [], |row| row.get(0))?;
+
+        Ok(last.unwrap_or(0) as u64)
+    }
+
+    /// Get all fills with the id of the note that produced them
+    pub fn get_fills(&self) -> Result<Vec<(i64, Fill)>, Box<dyn std::error::Error>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT note_id, fill_json FROM fills ORDER BY id ASC")?;
+
+        let fills_iter = stmt.query_map([], |row| {
+            let note_id: i64 = row.get(0)?;
+            let fill_json: String = row.get(1)?;
+            Ok((note_id, fill_json))
+        })?;
+
+        let mut fills = Vec::new();
+        for fill_result in fills_iter {
+            let (note_id, fill_json) = fill_result?;
+            let fill: Fill = serde_json::from_str(&fill_json)?;
+            fills.push((note_id, fill));
+        }
+
+        Ok(fills)
+    }
+
+    /// Record the response to a note, unless the note already has one of this kind
+    ///
+    /// Returns whether the response was recorded.
+    pub fn insert_response(
+        &self,
+        note_id: i64,
+        kind: ResponseKind,
+        response: &MosaicNote,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let note_json = serde_json::to_string(response)?;
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_secs() as i64;
+
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO responses (note_id, kind, note_json, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![note_id, kind.as_ref(), note_json, now],
+        )?;
+
+        Ok(inserted == 1)
+    }
+
+    /// Replace the recorded response to a note
+    pub fn update_response(
+        &self,
+        note_id: i64,
+        kind: ResponseKind,
+        response: &MosaicNote,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let note_json = serde_json::to_string(response)?;
+        self.conn.execute(
+            "UPDATE responses SET note_json = ?1 WHERE note_id = ?2 AND kind = ?3",
+            params![note_json, note_id, kind.as_ref()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Forget the response to a note, once it could not be sent
+    pub fn delete_response(
+        &self,
+        note_id: i64,
+        kind: ResponseKind,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.conn.execute(
+            "DELETE FROM responses WHERE note_id = ?1 AND kind = ?2",
+            params![note_id, kind.as_ref()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get the response to a note, if any
+    pub fn get_response(
+        &self,
+        note_id: i64,
+        kind: ResponseKind,
+    ) -> Result<Option<MosaicNote>, Box<dyn std::error::Error>> {
+        let result = self.conn.query_row(
+            "SELECT note_json FROM responses WHERE note_id = ?1 AND kind = ?2",
+            params![note_id, kind.as_ref()],
+            |row| row.get::<_, String>(0),
+        );
+
+        match result {
+            Ok(note_json) => Ok(Some(serde_json::from_str(&note_json)?)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(Box::new(e)),
+        }
+    }
 }
@@ -1,13 +1,17 @@
-use mosaic_fi::note::{MosaicNote, MosaicNoteStatus};
+use mosaic_fi::book::{DeskStatus, FeeSchedule, Fill, OrderBook, OrderLimits};
+use mosaic_fi::note::{Amount, MosaicNote, MosaicNoteStatus, Order, Price, Scale, Side};
 use mosaic_fi::{AccountOrder, AccountOrderResult, AccountType, Market};
+use mosaic_miden::auto_sync::{AutoSync, ConsumePolicy, ConsumeRule, SyncEvent};
 use mosaic_miden::client::ClientHandle;
 use mosaic_miden::store::{AssetRecord, OrderRecord, SettingsRecord};
 use mosaic_miden::{MidenTransactionId, Network};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::broadcast;
 use uuid::Uuid;
 pub mod desk_store;
-use desk_store::{DeskStore, NoteStatus};
+use desk_store::{DeskStore, NoteStatus, ResponseKind};
 pub mod asset_store;
 use asset_store::{StoredAsset as RegistryStoredAsset, default_assets};
 
@@ -86,6 +90,8 @@ pub struct Serve {
     desk_store_path: PathBuf,
     clients: HashMap<([u8; 32], Network), ClientHandle>,
     desks: HashMap<String, DeskMetadata>,
+    auto_sync: Option<Duration>,
+    syncs: HashMap<([u8; 32], Network), AutoSync>,
 }
 
 impl Serve {
@@ -104,6 +110,8 @@ impl Serve {
             desk_store_path,
             clients: HashMap::new(),
             desks: HashMap::new(),
+            auto_sync: None,
+            syncs: HashMap::new(),
         })
     }
 
@@ -129,7 +137,11 @@ impl Serve {
                 "Restoring desk"
             );
 
-            match ClientHandle::spawn(client_path.clone(), network).await {
+            let spawned = match network.config() {
+                Ok(config) => ClientHandle::spawn(client_path.clone(), config).await,
+                Err(e) => Err(e.into()),
+            };
+            match spawned {
                 Ok(client_handle) => {
                     let market_url = Self::resolve_market_url(&account_id, desk.market_url.clone());
                     let owner_account = desk.owner_account.clone().unwrap_or_default();
@@ -171,7 +183,23 @@ impl Serve {
         network: Network,
         market: Market,
         owner_account: String,
+        fees: FeeSchedule,
+        limits: OrderLimits,
     ) -> Result<(String, String), Box<dyn std::error::Error>> {
+        fees.check()?;
+        // Notional bounds and buy offer backing are stored per base unit, so
+        // they need the base decimals. Without them, the desk refuses backed
+        // buy offers.
+        let scale = match self.market_scale(secret, &market) {
+            Ok(scale) => Some(scale),
+            Err(_) if limits.min_notional == 0 && limits.max_notional == 0 => None,
+            Err(e) => return Err(e),
+        };
+        let limits_word = limits.storage_word(scale.unwrap_or_default())?;
+        let base_unit_word = match scale {
+            Some(scale) => mosaic_fi::book::base_unit_word(scale)?,
+            None => miden_objects::Word::default(),
+        };
         let path = self.client_path(secret, network);
         Self::check_or_create(&path)?;
 
@@ -198,6 +226,12 @@ impl Serve {
                 market.quote.code.clone(),
                 market.quote.issuer.clone(),
                 owner_account_id,
+                mosaic_miden::client::DeskSettings {
+                    status: DeskStatus::Open.storage_word(),
+                    fees: fees.storage_word(),
+                    limits: limits_word,
+                    base_unit: base_unit_word,
+                },
             )
             .await
             .map_err(|e| anyhow::anyhow!("Failed to create desk account: {}", e))?;
@@ -216,6 +250,7 @@ impl Serve {
         let store_path = self.store_path(secret, network);
         let store = mosaic_miden::store::Store::new(&store_path)?;
         store.insert_account(&account_id_bech32, network, "Desk", None)?;
+        self.refresh_auto_sync(secret, network)?;
 
         let market_url = Self::resolve_market_url(&account_id_bech32, remote_market_url);
 
@@ -226,6 +261,8 @@ impl Serve {
                 network,
                 market: market.clone(),
                 owner_account: owner_account.clone(),
+                fees,
+                limits,
             },
         )?;
 
@@ -254,6 +291,9 @@ impl Serve {
         };
         self.desks.insert(account_id_bech32.clone(), metadata);
 
+        // The desk is built open
+        desk_store.update_desk_status(&account_id_bech32, DeskStatus::Open)?;
+
         tracing::info!(
             account_id = %account_id_bech32,
             network = ?network,
@@ -290,6 +330,20 @@ impl Serve {
             .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
         let desk_path = stored_desk.path;
 
+        // Notes addressed to another account or network are refused
+        note.recipient
+            .check_network(stored_desk.network.to_network_id())?;
+        note.check_desk_recipient(Self::desk_account_id(desk_account)?)?;
+
+        // Orders for another market never reach the desk book
+        if let Some(market) = note.order.market() {
+            self.desk_check_market(desk_account, market).await?;
+        }
+
+        // Fill fees are computed on notionals in the decimals of the market,
+        // desks whose market assets are not registered record fills without fees
+        let scale = self.desk_scale(desk_account).ok();
+
         // Open the desk's note store
         let desk_note_store_path = desk_path.join("desk_notes.sqlite3");
         let desk_note_store = desk_store::DeskNoteStore::new(&desk_note_store_path)?;
@@ -306,18 +360,7 @@ impl Serve {
                 anyhow::anyhow!("Desk client handle not available for {}", desk_account)
             })?;
 
-        let (_network_id, address) = miden_objects::address::Address::from_bech32(desk_account)
-            .map_err(|e| anyhow::anyhow!("Invalid desk account {}: {}", desk_account, e))?;
-        let account_id = match address {
-            miden_objects::address::Address::AccountId(addr) => addr.id(),
-            _ => {
-                return Err(anyhow::anyhow!(
-                    "Desk account must resolve to an account id: {}",
-                    desk_account
-                )
-                .into());
-            }
-        };
+        let account_id = Self::desk_account_id(desk_account)?;
 
         match client_handle
             .consume_note(account_id, note.miden_note.miden_note_hex.clone())
@@ -331,6 +374,24 @@ impl Serve {
                     tx_id = %tx_id,
                     "Consumed note for desk"
                 );
+
+                if let Err(error) = Self::record_desk_fills(
+                    &client_handle,
+                    account_id,
+                    &desk_note_store_path,
+                    note_id,
+                    stored_desk.network,
+                    scale,
+                )
+                .await
+                {
+                    tracing::error!(
+                        desk_account = %desk_account,
+                        note_id = note_id,
+                        error = %error,
+                        "Failed to record desk fills"
+                    );
+                }
             }
             Err(error) => {
                 desk_note_store.update_note_status(note_id, NoteStatus::Invalid)?;
@@ -347,6 +408,214 @@ impl Serve {
         Ok(note_id)
     }
 
+    /// Record the fills executed by the desk since the last recorded one,
+    /// along with the fees charged on them
+    ///
+    /// Fill fees are informational, they are computed with the fee schedule
+    /// the desk recorded with each fill, or its current one for fills
+    /// recorded without, and are not collected on chain.
+    async fn record_desk_fills(
+        client_handle: &ClientHandle,
+        account_id: miden_objects::account::AccountId,
+        desk_note_store_path: &Path,
+        note_id: i64,
+        network: Network,
+        scale: Option<Scale>,
+    ) -> Result<Vec<Fill>, Box<dyn std::error::Error>> {
+        let account_record = client_handle
+            .get_account(account_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get desk account: {}", e))?
+            .ok_or_else(|| anyhow::anyhow!("Desk account not found: {}", account_id))?;
+
+        let desk_note_store = desk_store::DeskNoteStore::new(desk_note_store_path)?;
+        let last_fill_id = desk_note_store.last_fill_id()?;
+        let mut fills = mosaic_fi::book::read_fills(
+            account_record.account(),
+            last_fill_id,
+            network.to_network_id(),
+        )?;
+        let fees = mosaic_fi::book::read_fees(account_record.account())?;
+        Self::store_desk_fills(&desk_note_store, note_id, &mut fills, fees, scale)?;
+
+        for fill in &fills {
+            tracing::info!(
+                account_id = %account_id,
+                note_id = note_id,
+                fill_id = fill.id,
+                side = ?fill.side,
+                price = %fill.price,
+                amount = %fill.amount,
+                maker_fee = fill.maker_fee,
+                taker_fee = fill.taker_fee,
+                "Recorded desk fill"
+            );
+        }
+
+        Ok(fills)
+    }
+
+    /// Store fills in the note store of a desk, with their fees when the
+    /// decimals of the market are known and without fees otherwise
+    fn store_desk_fills(
+        desk_note_store: &desk_store::DeskNoteStore,
+        note_id: i64,
+        fills: &mut [Fill],
+        fees: FeeSchedule,
+        scale: Option<Scale>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        for fill in fills.iter_mut() {
+            if let Some(scale) = scale {
+                fill.charge_fees(fees, scale)?;
+            }
+            desk_note_store.insert_fill(note_id, fill)?;
+        }
+        Ok(())
+    }
+
+    /// Get all fills executed by a desk
+    pub async fn desk_get_fills(
+        &self,
+        desk_account: &str,
+    ) -> Result<Vec<Fill>, Box<dyn std::error::Error>> {
+        let desk_store = desk_store::DeskStore::new(&self.desk_store_path)?;
+        let stored_desk = desk_store
+            .get_desk(desk_account)?
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+
+        let desk_note_store_path = stored_desk.path.join("desk_notes.sqlite3");
+        let desk_note_store = desk_store::DeskNoteStore::new(&desk_note_store_path)?;
+
+        // Decimal quantities are only given once the market assets are registered
+        let scale = self.desk_scale(desk_account).ok();
+        let fills = desk_note_store
+            .get_fills()?
+            .into_iter()
+            .map(|(_, fill)| match scale {
+                Some(scale) => fill.with_decimals(scale),
+                None => fill,
+            })
+            .collect();
+
+        Ok(fills)
+    }
+
+    /// Read the current book of a desk from its account storage
+    pub async fn desk_get_order_book(
+        &self,
+        desk_account: &str,
+    ) -> Result<OrderBook, Box<dyn std::error::Error>> {
+        let (client_handle, network) = self
+            .desks
+            .get(desk_account)
+            .map(|metadata| (metadata.client_handle.clone(), metadata.network))
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+
+        let account_id = Self::desk_account_id(desk_account)?;
+        let account_record = client_handle
+            .get_account(account_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get desk account: {}", e))?
+            .ok_or_else(|| anyhow::anyhow!("Desk account not found: {}", account_id))?;
+
+        let book = mosaic_fi::book::read_book(account_record.account(), network.to_network_id())?;
+
+        // Decimal quantities are only given once the market assets are registered
+        Ok(match self.desk_scale(desk_account).ok() {
+            Some(scale) => book.with_decimals(scale),
+            None => book,
+        })
+    }
+
+    /// Read the trading status of a desk from its account storage
+    pub async fn desk_get_status(
+        &self,
+        desk_account: &str,
+    ) -> Result<DeskStatus, Box<dyn std::error::Error>> {
+        let client_handle = self
+            .desks
+            .get(desk_account)
+            .map(|metadata| metadata.client_handle.clone())
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+
+        let account_id = Self::desk_account_id(desk_account)?;
+        let account_record = client_handle
+            .get_account(account_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get desk account: {}", e))?
+            .ok_or_else(|| anyhow::anyhow!("Desk account not found: {}", account_id))?;
+
+        mosaic_fi::book::read_status(account_record.account())
+    }
+
+    /// Read the fee schedule of a desk from its account storage
+    pub async fn desk_get_fees(
+        &self,
+        desk_account: &str,
+    ) -> Result<FeeSchedule, Box<dyn std::error::Error>> {
+        let client_handle = self
+            .desks
+            .get(desk_account)
+            .map(|metadata| metadata.client_handle.clone())
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+
+        let account_id = Self::desk_account_id(desk_account)?;
+        let account_record = client_handle
+            .get_account(account_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get desk account: {}", e))?
+            .ok_or_else(|| anyhow::anyhow!("Desk account not found: {}", account_id))?;
+
+        mosaic_fi::book::read_fees(account_record.account())
+    }
+
+    /// Read the order limits of a desk from its account storage
+    ///
+    /// Notionals are stored in base units, so the decimals of the market
+    /// must be known to read them.
+    pub async fn desk_get_limits(
+        &self,
+        desk_account: &str,
+    ) -> Result<OrderLimits, Box<dyn std::error::Error>> {
+        let client_handle = self
+            .desks
+            .get(desk_account)
+            .map(|metadata| metadata.client_handle.clone())
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+
+        let account_id = Self::desk_account_id(desk_account)?;
+        let account_record = client_handle
+            .get_account(account_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get desk account: {}", e))?
+            .ok_or_else(|| anyhow::anyhow!("Desk account not found: {}", account_id))?;
+
+        let scale = self.desk_scale(desk_account)?;
+        mosaic_fi::book::read_limits(account_record.account(), scale)
+    }
+
+    /// Reject a market that the desk account is not configured to trade
+    pub async fn desk_check_market(
+        &self,
+        desk_account: &str,
+        market: &Market,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let client_handle = self
+            .desks
+            .get(desk_account)
+            .map(|metadata| metadata.client_handle.clone())
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+
+        let account_id = Self::desk_account_id(desk_account)?;
+        let account_record = client_handle
+            .get_account(account_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get desk account: {}", e))?
+            .ok_or_else(|| anyhow::anyhow!("Desk account not found: {}", account_id))?;
+
+        mosaic_fi::book::check_market(account_record.account(), market)
+    }
+
     /// Get all notes from a desk
     pub async fn desk_get_notes(
         &self,
@@ -369,6 +638,350 @@ impl Serve {
         Ok(notes)
     }
 
+    /// Answer a quote request consumed by a desk
+    ///
+    /// The response is a private note addressed to the account that sent the
+    /// request, with a firm price or a decline when no price is given.
+    pub async fn desk_respond_to_quote(
+        &self,
+        desk_account: &str,
+        note_id: i64,
+        price: Option<Price>,
+    ) -> Result<MosaicNote, Box<dyn std::error::Error>> {
+        let desk_store = desk_store::DeskStore::new(&self.desk_store_path)?;
+        let stored_desk = desk_store
+            .get_desk(desk_account)?
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+
+        let desk_note_store_path = stored_desk.path.join("desk_notes.sqlite3");
+        let desk_note_store = desk_store::DeskNoteStore::new(&desk_note_store_path)?;
+
+        let (request, status) = desk_note_store.get_note(note_id)?.ok_or_else(|| {
+            anyhow::anyhow!("Note {} not found for desk {}", note_id, desk_account)
+        })?;
+
+        if status != NoteStatus::Consumed {
+            return Err(anyhow::anyhow!(
+                "Quote request {} has not been consumed by desk {}",
+                note_id,
+                desk_account
+            )
+            .into());
+        }
+
+        let Order::QuoteRequest {
+            market,
+            uuid,
+            side,
+            amount,
+        } = request.order.clone()
+        else {
+            return Err(anyhow::anyhow!("Note {} is not a quote request", note_id).into());
+        };
+
+        let metadata = self
+            .desks
+            .get(desk_account)
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+
+        let account_id = Self::desk_account_id(desk_account)?;
+
+        let target_account_id = Self::note_sender(&request, metadata.network)?;
+
+        let order = match price {
+            Some(price) => Order::QuoteRequestOffer {
+                market,
+                uuid,
+                side,
+                amount,
+                price,
+                target_account_id,
+            },
+            None => Order::QuoteRequestNoOffer {
+                market,
+                uuid,
+                target_account_id,
+            },
+        };
+
+        let mut response = mosaic_fi::note::compile_note_from_account_id(account_id, order)?;
+
+        // A quote request is answered once, the answer is reserved before it is sent
+        if !desk_note_store.insert_response(note_id, ResponseKind::Quote, &response)? {
+            return Err(
+                anyhow::anyhow!("Quote request {} has already been answered", note_id).into(),
+            );
+        }
+
+        let tx_id = match metadata
+            .client_handle
+            .commit_note(account_id, response.miden_note.miden_note_hex.clone())
+            .await
+        {
+            Ok(tx_id) => tx_id,
+            Err(e) => {
+                desk_note_store.delete_response(note_id, ResponseKind::Quote)?;
+                return Err(anyhow::anyhow!("Failed to commit quote response: {}", e).into());
+            }
+        };
+        response.status = MosaicNoteStatus::Committed(tx_id.clone());
+        desk_note_store.update_response(note_id, ResponseKind::Quote, &response)?;
+
+        tracing::info!(
+            desk_account = %desk_account,
+            note_id = note_id,
+            tx_id = %tx_id,
+            offered = price.is_some(),
+            "Answered quote request"
+        );
+
+        Ok(response)
+    }
+
+    /// Settle a quote request consumed by a desk at a firm price
+    ///
+    /// The settlement note carries the asset the client takes from the desk
+    /// vault, and is consumed by the client only by paying the desk back. A
+    /// quote request is settled once, at the price the desk offered for it.
+    pub async fn desk_settle_quote(
+        &self,
+        desk_account: &str,
+        note_id: i64,
+        price: Price,
+    ) -> Result<MosaicNote, Box<dyn std::error::Error>> {
+        let desk_store = desk_store::DeskStore::new(&self.desk_store_path)?;
+        let stored_desk = desk_store
+            .get_desk(desk_account)?
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+
+        let desk_note_store_path = stored_desk.path.join("desk_notes.sqlite3");
+        let desk_note_store = desk_store::DeskNoteStore::new(&desk_note_store_path)?;
+
+        let (request, status) = desk_note_store.get_note(note_id)?.ok_or_else(|| {
+            anyhow::anyhow!("Note {} not found for desk {}", note_id, desk_account)
+        })?;
+
+        if status != NoteStatus::Consumed {
+            return Err(anyhow::anyhow!(
+                "Quote request {} has not been consumed by desk {}",
+                note_id,
+                desk_account
+            )
+            .into());
+        }
+
+        let Order::QuoteRequest {
+            market,
+            uuid,
+            side,
+            amount,
+        } = request.order.clone()
+        else {
+            return Err(anyhow::anyhow!("Note {} is not a quote request", note_id).into());
+        };
+
+        let metadata = self
+            .desks
+            .get(desk_account)
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+        let account_id = Self::desk_account_id(desk_account)?;
+        let target_account_id = Self::note_sender(&request, metadata.network)?;
+
+        let offer = desk_note_store.get_response(note_id, ResponseKind::Quote)?;
+        Self::check_settlement_price(offer.as_ref().map(|offer| &offer.order), price)?;
+
+        // The client pays the taker fee of the desk
+        let scale = self.desk_scale(desk_account)?;
+        let fees = self.desk_get_fees(desk_account).await?;
+        let fee = fees.taker_fee(price.notional(amount, scale)?);
+
+        let order = Order::Settle {
+            market,
+            uuid,
+            side,
+            amount,
+            price,
+            target_account_id,
+            fee,
+            nonce: rand::random(),
+        };
+        let mut settlement =
+            mosaic_fi::note::compile_backed_note_from_account_id(account_id, order, None, scale)?;
+
+        if !desk_note_store.insert_response(note_id, ResponseKind::Settlement, &settlement)? {
+            return Err(
+                anyhow::anyhow!("Quote request {} has already been settled", note_id).into(),
+            );
+        }
+
+        let tx_id = match metadata
+            .client_handle
+            .commit_note(account_id, settlement.miden_note.miden_note_hex.clone())
+            .await
+        {
+            Ok(tx_id) => tx_id,
+            Err(e) => {
+                desk_note_store.delete_response(note_id, ResponseKind::Settlement)?;
+                return Err(anyhow::anyhow!("Failed to commit settlement: {}", e).into());
+            }
+        };
+        settlement.status = MosaicNoteStatus::Committed(tx_id.clone());
+        desk_note_store.update_response(note_id, ResponseKind::Settlement, &settlement)?;
+
+        tracing::info!(
+            desk_account = %desk_account,
+            note_id = note_id,
+            tx_id = %tx_id,
+            price = %price,
+            fee = fee,
+            "Committed settlement"
+        );
+
+        Ok(settlement)
+    }
+
+    /// Collect the payment of a settlement consumed by the client
+    pub async fn desk_collect_settlement(
+        &self,
+        desk_account: &str,
+        settlement: &MosaicNote,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let metadata = self
+            .desks
+            .get(desk_account)
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+        let account_id = Self::desk_account_id(desk_account)?;
+
+        let payback = mosaic_fi::note::compile_settlement_payback(
+            account_id,
+            &settlement.order,
+            self.desk_scale(desk_account)?,
+        )?;
+
+        let tx_id = metadata
+            .client_handle
+            .consume_note(account_id, payback.miden_note_hex)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to collect settlement: {}", e))?;
+
+        tracing::info!(
+            desk_account = %desk_account,
+            tx_id = %tx_id,
+            "Collected settlement"
+        );
+
+        Ok(tx_id)
+    }
+
+    /// Acknowledge a limit order pushed to a desk
+    ///
+    /// Orders the desk consumed rest in its book and are acknowledged as locked,
+    /// orders the desk failed to consume are acknowledged as not locked.
+    pub async fn desk_acknowledge_limit_order(
+        &self,
+        desk_account: &str,
+        note_id: i64,
+    ) -> Result<MosaicNote, Box<dyn std::error::Error>> {
+        let desk_store = desk_store::DeskStore::new(&self.desk_store_path)?;
+        let stored_desk = desk_store
+            .get_desk(desk_account)?
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+
+        let desk_note_store_path = stored_desk.path.join("desk_notes.sqlite3");
+        let desk_note_store = desk_store::DeskNoteStore::new(&desk_note_store_path)?;
+
+        let (request, status) = desk_note_store.get_note(note_id)?.ok_or_else(|| {
+            anyhow::anyhow!("Note {} not found for desk {}", note_id, desk_account)
+        })?;
+
+        let Order::LimitOrder {
+            market, uuid, side, ..
+        } = request.order.clone()
+        else {
+            return Err(anyhow::anyhow!("Note {} is not a limit order", note_id).into());
+        };
+
+        let locked = match status {
+            NoteStatus::Consumed => true,
+            NoteStatus::Invalid => false,
+            NoteStatus::New => {
+                return Err(anyhow::anyhow!(
+                    "Limit order {} has not been processed by desk {}",
+                    note_id,
+                    desk_account
+                )
+                .into());
+            }
+        };
+
+        let metadata = self
+            .desks
+            .get(desk_account)
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+
+        let account_id = Self::desk_account_id(desk_account)?;
+
+        let target_account_id = Self::note_sender(&request, metadata.network)?;
+
+        let order = match (side, locked) {
+            (Side::BUY, true) => Order::LimitBuyOrderLocked {
+                market,
+                uuid,
+                target_account_id,
+            },
+            (Side::BUY, false) => Order::LimitBuyOrderNotLocked {
+                market,
+                uuid,
+                target_account_id,
+            },
+            (Side::SELL, true) => Order::LimitSellOrderLocked {
+                market,
+                uuid,
+                target_account_id,
+            },
+            (Side::SELL, false) => Order::LimitSellOrderNotLocked {
+                market,
+                uuid,
+                target_account_id,
+            },
+        };
+
+        let mut response = mosaic_fi::note::compile_note_from_account_id(account_id, order)?;
+
+        // A limit order is acknowledged once, the acknowledgement is reserved before it is sent
+        if !desk_note_store.insert_response(note_id, ResponseKind::LimitOrderAck, &response)? {
+            return Err(
+                anyhow::anyhow!("Limit order {} has already been acknowledged", note_id).into(),
+            );
+        }
+
+        let tx_id = match metadata
+            .client_handle
+            .commit_note(account_id, response.miden_note.miden_note_hex.clone())
+            .await
+        {
+            Ok(tx_id) => tx_id,
+            Err(e) => {
+                desk_note_store.delete_response(note_id, ResponseKind::LimitOrderAck)?;
+                return Err(
+                    anyhow::anyhow!("Failed to commit limit order acknowledgement: {}", e).into(),
+                );
+            }
+        };
+        response.status = MosaicNoteStatus::Committed(tx_id.clone());
+        desk_note_store.update_response(note_id, ResponseKind::LimitOrderAck, &response)?;
+
+        tracing::info!(
+            desk_account = %desk_account,
+            note_id = note_id,
+            tx_id = %tx_id,
+            locked,
+            "Acknowledged limit order"
+        );
+
+        Ok(response)
+    }
+
     /// Get desk information including market data from in-memory cache
     pub async fn get_desk_info(
         &self,
@@ -446,6 +1059,14 @@ impl Serve {
         bs58::encode(secret).into_string()
     }
 
+    fn secret_from_string(secret: &str) -> Result<[u8; 32], anyhow::Error> {
+        bs58::decode(secret)
+            .into_vec()
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| anyhow::anyhow!("Invalid secret identifier"))
+    }
+
     fn network_from_account(account: &str) -> Result<Network, anyhow::Error> {
         let (network_id, _) = miden_objects::address::Address::from_bech32(account)
             .map_err(|e| anyhow::anyhow!("Invalid account '{}': {}", account, e))?;
@@ -459,6 +1080,89 @@ impl Serve {
         })
     }
 
+    /// Parse a bech32 account and check it is on the network
+    fn account_id_on(
+        account: &str,
+        network: Network,
+    ) -> Result<miden_objects::account::AccountId, anyhow::Error> {
+        let (network_id, address) = miden_objects::address::Address::from_bech32(account)
+            .map_err(|e| anyhow::anyhow!("Invalid account '{}': {}", account, e))?;
+        if network_id != network.to_network_id() {
+            return Err(anyhow::anyhow!("Account {} is not on {}", account, network));
+        }
+        match address {
+            miden_objects::address::Address::AccountId(addr) => Ok(addr.id()),
+            _ => Err(anyhow::anyhow!(
+                "Account must resolve to an account id: {}",
+                account
+            )),
+        }
+    }
+
+    /// Check that a quote request is settled at the price the desk offered
+    fn check_settlement_price(offer: Option<&Order>, price: Price) -> Result<(), anyhow::Error> {
+        match offer {
+            Some(Order::QuoteRequestOffer { price: offered, .. }) if *offered == price => Ok(()),
+            Some(Order::QuoteRequestOffer { price: offered, .. }) => Err(anyhow::anyhow!(
+                "Settlement price {} differs from the offered price {}",
+                price,
+                offered
+            )),
+            _ => Err(anyhow::anyhow!(
+                "Quote request has not been answered with an offer"
+            )),
+        }
+    }
+
+    fn desk_account_id(
+        desk_account: &str,
+    ) -> Result<miden_objects::account::AccountId, anyhow::Error> {
+        let (_network_id, address) = miden_objects::address::Address::from_bech32(desk_account)
+            .map_err(|e| anyhow::anyhow!("Invalid desk account {}: {}", desk_account, e))?;
+        match address {
+            miden_objects::address::Address::AccountId(addr) => Ok(addr.id()),
+            _ => Err(anyhow::anyhow!(
+                "Desk account must resolve to an account id: {}",
+                desk_account
+            )),
+        }
+    }
+
+    /// Bech32 account id of the account that emitted a note
+    fn note_sender(
+        note: &MosaicNote,
+        network: Network,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let sender = mosaic_miden::note::decode_note(&note.miden_note)?
+            .metadata()
+            .sender();
+        let address = miden_objects::address::AccountIdAddress::new(
+            sender,
+            miden_objects::address::AddressInterface::Unspecified,
+        );
+        Ok(miden_objects::address::Address::from(address).to_bech32(network.to_network_id()))
+    }
+
+    fn compile_order(
+        account_id: miden_objects::account::AccountId,
+        order: mosaic_fi::note::Order,
+        desk_account: Option<&str>,
+        backing: Option<Scale>,
+    ) -> Result<MosaicNote, Box<dyn std::error::Error>> {
+        match (backing, desk_account) {
+            (Some(scale), desk_account) => mosaic_fi::note::compile_backed_note_from_account_id(
+                account_id,
+                order,
+                desk_account,
+                scale,
+            ),
+            (None, Some(desk_account)) => {
+                mosaic_fi::note::compile_desk_note_from_account_id(account_id, order, desk_account)
+            }
+            (None, None) => mosaic_fi::note::compile_note_from_account_id(account_id, order),
+        }
+    }
+
     fn order_metadata(order: &mosaic_fi::note::Order) -> (String, Option<String>) {
         use mosaic_fi::note::Order::*;
 
@@ -473,11 +1177,24 @@ impl Serve {
             QuoteRequest { uuid, .. } => ("QuoteRequest".to_string(), Some(uuid.to_string())),
             LimitOrder { uuid, .. } => ("LimitOrder".to_string(), Some(uuid.to_string())),
             LiquidityOffer { uuid, .. } => ("LiquidityOffer".to_string(), Some(uuid.to_string())),
+            // The cancelled offer keeps its own record
+            CancelOffer { .. } => ("CancelOffer".to_string(), None),
             FundAccount { .. } => ("FundAccount".to_string(), None),
-            LimitBuyOrderLocked => ("LimitBuyOrderLocked".to_string(), None),
-            LimitBuyOrderNotLocked => ("LimitBuyOrderNotLocked".to_string(), None),
-            LimitSellOrderLocked => ("LimitSellOrderLocked".to_string(), None),
-            LimitSellOrderNotLocked => ("LimitSellOrderNotLocked".to_string(), None),
+            Transfer { .. } => ("Transfer".to_string(), None),
+            LimitBuyOrderLocked { uuid, .. } => {
+                ("LimitBuyOrderLocked".to_string(), Some(uuid.to_string()))
+            }
+            LimitBuyOrderNotLocked { uuid, .. } => {
+                ("LimitBuyOrderNotLocked".to_string(), Some(uuid.to_string()))
+            }
+            LimitSellOrderLocked { uuid, .. } => {
+                ("LimitSellOrderLocked".to_string(), Some(uuid.to_string()))
+            }
+            LimitSellOrderNotLocked { uuid, .. } => (
+                "LimitSellOrderNotLocked".to_string(),
+                Some(uuid.to_string()),
+            ),
+            Settle { uuid, .. } => ("Settle".to_string(), Some(uuid.to_string())),
         }
     }
 
@@ -503,10 +1220,7 @@ impl Serve {
 
     fn client_path(&self, secret: [u8; 32], network: Network) -> PathBuf {
         let secret_str = Self::secret_to_string(secret);
-        let network_prefix = match network {
-            Network::Testnet => "testnet",
-            Network::Localnet => "localnet",
-        };
+        let network_prefix = network.as_str().to_ascii_lowercase();
         let dir_name = format!("{}_{}", network_prefix, secret_str);
         self.store_path.join(dir_name)
     }
@@ -531,7 +1245,7 @@ impl Serve {
         let mut client_accounts = Vec::new();
 
         // Collect client-managed accounts from each network store
-        for network in [Network::Testnet, Network::Localnet] {
+        for network in Network::ALL {
             let store_path = self.store_path(secret, network);
 
             if !store_path.exists() {
@@ -633,7 +1347,7 @@ impl Serve {
             assets_map.insert(key, asset);
         }
 
-        for network in [Network::Testnet, Network::Localnet] {
+        for network in Network::ALL {
             let client_dir = self.client_path(secret, network);
             if !client_dir.exists() {
                 continue;
@@ -664,13 +1378,70 @@ impl Serve {
         Ok(assets_map.into_values().collect())
     }
 
+    /// Decimals of the faucets of a market, from the asset registry of the user
+    pub fn market_scale(
+        &self,
+        secret: [u8; 32],
+        market: &Market,
+    ) -> Result<Scale, Box<dyn std::error::Error>> {
+        let assets = self.list_assets_for_user(secret)?;
+        let decimals = |issuer: &str| {
+            assets
+                .iter()
+                .find(|asset| asset.account == issuer)
+                .map(|asset| asset.decimals)
+                .ok_or_else(|| anyhow::anyhow!("Asset {} is not registered", issuer))
+        };
+
+        Ok(Scale {
+            base_decimals: decimals(&market.base.issuer)?,
+            quote_decimals: decimals(&market.quote.issuer)?,
+        })
+    }
+
+    /// Set the amount and price of an order from decimal values such as "65000.25"
+    ///
+    /// The decimals come from the registered faucets of the order market.
+    pub fn scale_order(
+        &self,
+        secret: [u8; 32],
+        order: Order,
+        amount: Option<&str>,
+        price: Option<&str>,
+    ) -> Result<Order, Box<dyn std::error::Error>> {
+        if amount.is_none() && price.is_none() {
+            return Ok(order);
+        }
+
+        let market = order
+            .market()
+            .ok_or_else(|| anyhow::anyhow!("Order has no market to scale its quantities"))?;
+        let scale = self.market_scale(secret, market)?;
+
+        let amount = amount
+            .map(|amount| Amount::parse(amount, scale))
+            .transpose()?;
+        let price = price.map(|price| Price::parse(price, scale)).transpose()?;
+        order.with_quantities(amount, price)
+    }
+
+    /// Decimals of the market of a desk, from the registry of its owner
+    pub fn desk_scale(&self, desk_account: &str) -> Result<Scale, Box<dyn std::error::Error>> {
+        let metadata = self
+            .desks
+            .get(desk_account)
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+        let owner_secret = Self::secret_from_string(&metadata.owner_identifier)?;
+        self.market_scale(owner_secret, &metadata.market)
+    }
+
     pub fn list_orders_for_user(
         &self,
         secret: [u8; 32],
     ) -> Result<Vec<StoredOrder>, Box<dyn std::error::Error>> {
         let mut orders = Vec::new();
 
-        for network in [Network::Testnet, Network::Localnet] {
+        for network in Network::ALL {
             let client_dir = self.client_path(secret, network);
             if !client_dir.exists() {
                 continue;
@@ -760,14 +1531,86 @@ impl Serve {
 
         let path = self.client_path(secret, network);
 
-        let client_handle = ClientHandle::spawn(path, network).await?;
+        let client_handle = ClientHandle::spawn(path, network.config()?).await?;
 
         self.clients
             .insert((secret, network), client_handle.clone());
 
+        if let Some(interval) = self.auto_sync {
+            let policy = self.consume_policy(secret, network).unwrap_or_else(|e| {
+                tracing::warn!(error = %e, network = %network, "Failed to build consume policy");
+                ConsumePolicy::default()
+            });
+            self.syncs.insert(
+                (secret, network),
+                AutoSync::spawn(client_handle.clone(), network, interval, policy),
+            );
+        }
+
         Ok(client_handle)
     }
 
+    /// Sync the clients spawned from now on in the background, on an interval
+    ///
+    /// Payments to client, liquidity and desk accounts are consumed as soon
+    /// as a sync discovers them.
+    pub fn enable_auto_sync(&mut self, interval: Duration) {
+        self.auto_sync = Some(interval);
+    }
+
+    /// Subscribe to the background sync events of the client of a user
+    ///
+    /// Returns None when the client is not synced in the background.
+    pub fn subscribe_sync_events(
+        &self,
+        secret: [u8; 32],
+        network: Network,
+    ) -> Option<broadcast::Receiver<SyncEvent>> {
+        self.syncs.get(&(secret, network)).map(AutoSync::subscribe)
+    }
+
+    /// Notes consumed automatically by the accounts of a user on a network
+    fn consume_policy(
+        &self,
+        secret: [u8; 32],
+        network: Network,
+    ) -> Result<ConsumePolicy, Box<dyn std::error::Error>> {
+        let store_path = self.store_path(secret, network);
+        let store = mosaic_miden::store::Store::new(&store_path)?;
+
+        let mut policy = ConsumePolicy::default();
+        for (account, account_type, _) in store.list_accounts_by_network(network)? {
+            let Ok(account_id) = Self::account_id_on(&account, network) else {
+                continue;
+            };
+            match account_type.as_str() {
+                // Mosaic orders to desks go through desk_push_note, which
+                // records them in the desk's note store and book
+                "Client" | "Liquidity" | "Desk" => {
+                    policy.add_rule(account_id, ConsumeRule::Payments)
+                }
+                _ => {}
+            }
+        }
+        Ok(policy)
+    }
+
+    /// Apply the accounts of a user to their background sync, if any
+    fn refresh_auto_sync(
+        &mut self,
+        secret: [u8; 32],
+        network: Network,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.syncs.contains_key(&(secret, network)) {
+            return Ok(());
+        }
+        let policy = self.consume_policy(secret, network)?;
+        if let Some(sync) = self.syncs.get(&(secret, network)) {
+            sync.set_policy(policy);
+        }
+        Ok(())
+    }
+
     pub async fn new_account(
         &mut self,
         secret: [u8; 32],
@@ -814,6 +1657,7 @@ impl Serve {
             account_type_str,
             account_name.as_deref(),
         )?;
+        self.refresh_auto_sync(secret, network)?;
 
         match account_type {
             AccountType::Client => {
@@ -882,6 +1726,129 @@ impl Serve {
         Ok(account_id_bech32)
     }
 
+    /// Mint from a faucet of the user into a P2ID note for the target account
+    ///
+    /// The mint is recorded as a FundAccount order of the faucet. The returned
+    /// note is private and must be handed to the target to be consumed.
+    pub async fn mint(
+        &mut self,
+        secret: [u8; 32],
+        faucet_account: String,
+        target_account: String,
+        amount: Amount,
+    ) -> Result<MosaicNote, Box<dyn std::error::Error>> {
+        let network = Self::network_from_account(&faucet_account)?;
+        let faucet_id = Self::account_id_on(&faucet_account, network)?;
+        let target_id = Self::account_id_on(&target_account, network)?;
+
+        let client_handle = self.get_client(secret, network).await?;
+        let order = Order::FundAccount {
+            target_account_id: target_account,
+            amount,
+        };
+        let result = client_handle
+            .mint(faucet_id, target_id, amount.units())
+            .await
+            .map_err(|e| format!("Failed to mint from {}: {}", faucet_account, e));
+
+        self.record_paid_order(secret, network, &faucet_account, order, result)
+    }
+
+    /// Transfer an asset from a wallet of the user into a P2ID note for the target
+    ///
+    /// With `reclaim_after`, the note is a P2IDE note the sender can reclaim
+    /// after that many blocks. The transfer is recorded as a Transfer order of
+    /// the sender, the returned note must be handed to the target.
+    pub async fn transfer(
+        &mut self,
+        secret: [u8; 32],
+        sender_account: String,
+        target_account: String,
+        asset: String,
+        amount: Amount,
+        reclaim_after: Option<u32>,
+    ) -> Result<MosaicNote, Box<dyn std::error::Error>> {
+        let network = Self::network_from_account(&sender_account)?;
+        let sender_id = Self::account_id_on(&sender_account, network)?;
+        let target_id = Self::account_id_on(&target_account, network)?;
+        let faucet_id = Self::account_id_on(&asset, network)?;
+        if sender_id == target_id {
+            return Err(anyhow::anyhow!("Cannot transfer to the sending account").into());
+        }
+
+        let client_handle = self.get_client(secret, network).await?;
+        let order = Order::Transfer {
+            target_account_id: target_account,
+            asset,
+            amount,
+            reclaim_after,
+        };
+        let result = client_handle
+            .transfer(
+                sender_id,
+                target_id,
+                faucet_id,
+                amount.units(),
+                reclaim_after,
+            )
+            .await
+            .map_err(|e| format!("Failed to transfer from {}: {}", sender_account, e));
+
+        self.record_paid_order(secret, network, &sender_account, order, result)
+    }
+
+    /// Record an order executed by a transaction of the account, returning its note
+    fn record_paid_order(
+        &self,
+        secret: [u8; 32],
+        network: Network,
+        account: &str,
+        order: Order,
+        result: Result<(MidenTransactionId, mosaic_miden::note::MidenNote), String>,
+    ) -> Result<MosaicNote, Box<dyn std::error::Error>> {
+        let store_path = self.store_path(secret, network);
+        let store = mosaic_miden::store::Store::new(&store_path)?;
+
+        let (order_type, _) = Self::order_metadata(&order);
+        let mut order_record = OrderRecord {
+            uuid: Uuid::new_v4().to_string(),
+            order_type,
+            order_json: serde_json::to_string(&order)?,
+            stage: "create".to_string(),
+            status: String::new(),
+            account: account.to_string(),
+            created_at: None,
+        };
+
+        let (tx_id, miden_note) = match result {
+            Ok(paid) => paid,
+            Err(e) => {
+                order_record.status = "failed".to_string();
+                let _ = store.upsert_order(&order_record);
+                return Err(anyhow::anyhow!(e).into());
+            }
+        };
+
+        order_record.status = "committed".to_string();
+        store.upsert_order(&order_record)?;
+
+        let recipient = match &order {
+            Order::FundAccount {
+                target_account_id, ..
+            }
+            | Order::Transfer {
+                target_account_id, ..
+            } => mosaic_fi::note::Recipient::client(target_account_id)?,
+            _ => mosaic_fi::note::Recipient::broadcast(),
+        };
+        Ok(MosaicNote {
+            status: MosaicNoteStatus::Committed(tx_id),
+            recipient,
+            order,
+            miden_note,
+        })
+    }
+
     pub async fn create_account_order(
         &mut self,
         secret: [u8; 32],
@@ -913,9 +1880,18 @@ impl Serve {
                 network,
                 market,
                 owner_account,
+                fees,
+                limits,
             } => {
                 let (account_id, market_url) = self
-                    .new_desk_account(secret, network, market.clone(), owner_account.clone())
+                    .new_desk_account(
+                        secret,
+                        network,
+                        market.clone(),
+                        owner_account.clone(),
+                        fees,
+                        limits,
+                    )
                     .await?;
 
                 Ok(AccountOrderResult::Desk {
@@ -996,22 +1972,137 @@ impl Serve {
 
     async fn activate_desk_account(
         &mut self,
-        _secret: [u8; 32],
-        _desk_account: &str,
-        _owner_account: &str,
+        secret: [u8; 32],
+        desk_account: &str,
+        owner_account: &str,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!("create note, commit and consume");
+        self.update_desk_status(secret, desk_account, owner_account, DeskStatus::Open)
+            .await?;
+
+        let network = self.desk_network(desk_account)?;
+        let store = mosaic_miden::store::Store::new(self.store_path(secret, network))?;
+        Self::record_account_order(
+            &store,
+            desk_account,
+            &AccountOrder::ActivateDesk {
+                desk_account: desk_account.to_string(),
+                owner_account: owner_account.to_string(),
+            },
+        )
     }
 
     async fn deactivate_desk_account(
         &mut self,
-        _secret: [u8; 32],
-        _desk_account: &str,
-        _owner_account: &str,
+        secret: [u8; 32],
+        desk_account: &str,
+        owner_account: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_desk_status(secret, desk_account, owner_account, DeskStatus::Closed)
+            .await?;
+
+        let network = self.desk_network(desk_account)?;
+        let store = mosaic_miden::store::Store::new(self.store_path(secret, network))?;
+        Self::record_account_order(
+            &store,
+            desk_account,
+            &AccountOrder::DeactivateDesk {
+                desk_account: desk_account.to_string(),
+                owner_account: owner_account.to_string(),
+            },
+        )
+    }
+
+    /// Set the trading status of a desk owned by the caller
+    ///
+    /// The owner account commits a status note addressed to the desk, which
+    /// the desk consumes right away. The resulting status is kept in the desk
+    /// store.
+    pub async fn update_desk_status(
+        &mut self,
+        secret: [u8; 32],
+        desk_account: &str,
+        owner_account: &str,
+        status: DeskStatus,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!("create note, commit and consume");
+        let desk_store = DeskStore::new(&self.desk_store_path)?;
+        let stored_desk = desk_store
+            .get_desk(desk_account)?
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account))?;
+
+        if stored_desk.owner_identifier != Self::secret_to_string(secret) {
+            return Err(anyhow::anyhow!("Desk {} is not owned by this user", desk_account).into());
+        }
+        if stored_desk
+            .owner_account
+            .as_deref()
+            .is_some_and(|owner| !owner.is_empty() && owner != owner_account)
+        {
+            return Err(anyhow::anyhow!(
+                "Account {} is not the owner of desk {}",
+                owner_account,
+                desk_account
+            )
+            .into());
+        }
+
+        let (_, owner_address) = miden_objects::address::Address::from_bech32(owner_account)
+            .map_err(|e| anyhow::anyhow!("Invalid owner account '{}': {}", owner_account, e))?;
+        let owner_account_id = match owner_address {
+            miden_objects::address::Address::AccountId(account_id_addr) => account_id_addr.id(),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Owner account must resolve to an account id: {}",
+                    owner_account
+                )
+                .into());
+            }
+        };
+        let account_id = Self::desk_account_id(desk_account)?;
+
+        let note = mosaic_fi::note::compile_desk_status_note(
+            owner_account_id,
+            desk_account,
+            Uuid::new_v4().as_u128(),
+            status,
+        )?;
+
+        let client_handle = self.get_client(secret, stored_desk.network).await?;
+        let tx_commit_id = client_handle
+            .commit_note(owner_account_id, note.miden_note_hex.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to commit status note: {}", e))?;
+        let tx_consume_id = client_handle
+            .consume_note(account_id, note.miden_note_hex.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to consume status note: {}", e))?;
+
+        desk_store.update_desk_status(desk_account, status)?;
+
+        tracing::info!(
+            desk_account = %desk_account,
+            owner_account = %owner_account,
+            status = ?status,
+            tx_commit_id = %tx_commit_id,
+            tx_consume_id = %tx_consume_id,
+            "Updated desk status"
+        );
+
+        Ok(())
+    }
+
+    fn desk_network(&self, desk_account: &str) -> Result<Network, Box<dyn std::error::Error>> {
+        self.desks
+            .get(desk_account)
+            .map(|metadata| metadata.network)
+            .ok_or_else(|| anyhow::anyhow!("Desk not found: {}", desk_account).into())
     }
 
+    /// Create an order note from an account, optionally committing it
+    ///
+    /// Orders consumed by a desk are addressed to the given desk account, or
+    /// broadcast when there is none. Backed notes carry the offered asset
+    /// from the faucets of the order market.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_private_note(
         &mut self,
         secret: [u8; 32],
@@ -1019,7 +2110,62 @@ impl Serve {
         account_id_bech32: String,
         order: mosaic_fi::note::Order,
         commit: bool,
+        desk_account: Option<String>,
+        backed: bool,
     ) -> Result<MosaicNote, Box<dyn std::error::Error>> {
+        order.check_quantities()?;
+
+        // A committed funding is minted and a committed transfer is paid by the
+        // wallet, neither note can be compiled on its own
+        if let Order::FundAccount {
+            target_account_id,
+            amount,
+        } = &order
+            && commit
+        {
+            if Self::network_from_account(&account_id_bech32)? != network {
+                return Err(
+                    anyhow::anyhow!("Faucet {} is not on {}", account_id_bech32, network).into(),
+                );
+            }
+            return self
+                .mint(
+                    secret,
+                    account_id_bech32,
+                    target_account_id.clone(),
+                    *amount,
+                )
+                .await;
+        }
+
+        if let Some(desk_account) = &desk_account {
+            let (desk_network_id, _) = miden_objects::address::Address::from_bech32(desk_account)?;
+            if desk_network_id != network.to_network_id() {
+                return Err(anyhow::anyhow!("Desk {} is not on {}", desk_account, network).into());
+            }
+            if let Some(market) = order.market() {
+                self.desk_check_market(desk_account, market).await?;
+            }
+
+            // Refuse offers the desk book would reject, before committing a note
+            if let Order::LimitOrder { amount, price, .. }
+            | Order::LiquidityOffer { amount, price, .. } = &order
+            {
+                let limits = self.desk_get_limits(desk_account).await?;
+                let scale = self.desk_scale(desk_account)?;
+                limits.check(*amount, *price, scale)?;
+            }
+        }
+
+        let backing = if backed {
+            let market = order
+                .market()
+                .ok_or_else(|| anyhow::anyhow!("Only market orders can be backed"))?;
+            Some(self.market_scale(secret, market)?)
+        } else {
+            None
+        };
+
         let client_handle = self.get_client(secret, network).await?;
 
         let (_network_id, address) =
@@ -1057,15 +2203,15 @@ impl Serve {
             created_at: None,
         };
 
-        let mut mosaic_note = match mosaic_fi::note::compile_note_from_account_id(account_id, order)
-        {
-            Ok(note) => note,
-            Err(err) => {
-                order_record.status = "failed".to_string();
-                let _ = store.upsert_order(&order_record);
-                return Err(err);
-            }
-        };
+        let mut mosaic_note =
+            match Self::compile_order(account_id, order, desk_account.as_deref(), backing) {
+                Ok(note) => note,
+                Err(err) => {
+                    order_record.status = "failed".to_string();
+                    let _ = store.upsert_order(&order_record);
+                    return Err(err);
+                }
+            };
 
         if commit {
             match client_handle
@@ -1097,6 +2243,7 @@ impl Serve {
         Ok(mosaic_note)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_note_from_masm(
         &mut self,
         secret: [u8; 32],
@@ -1304,11 +2451,64 @@ impl Serve {
         Ok(account_status)
     }
 
+    /// List the input notes discovered by sync that the account can consume
+    pub async fn list_consumable_notes(
+        &mut self,
+        secret: [u8; 32],
+        network: Network,
+        account_id_bech32: String,
+    ) -> Result<Vec<mosaic_miden::ConsumableNoteData>, Box<dyn std::error::Error>> {
+        let account_id = Self::account_id_on(&account_id_bech32, network)?;
+        let client_handle = self.get_client(secret, network).await?;
+
+        let notes = client_handle
+            .list_consumable_notes(account_id, network)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to list consumable notes: {}", e))?;
+        Ok(notes)
+    }
+
+    /// Consume input notes discovered by sync, given their ids in hex format
+    pub async fn consume_notes(
+        &mut self,
+        secret: [u8; 32],
+        network: Network,
+        account_id_bech32: String,
+        note_ids: Vec<String>,
+    ) -> Result<MidenTransactionId, Box<dyn std::error::Error>> {
+        let account_id = Self::account_id_on(&account_id_bech32, network)?;
+        let note_ids = Self::parse_note_ids(&note_ids)?;
+        let client_handle = self.get_client(secret, network).await?;
+
+        let tx_id = client_handle
+            .consume_notes(account_id, note_ids)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to consume notes: {}", e))?;
+        Ok(tx_id)
+    }
+
+    /// Parse note ids in hex format, refusing an empty list
+    fn parse_note_ids(
+        note_ids: &[String],
+    ) -> Result<Vec<miden_objects::note::NoteId>, anyhow::Error> {
+        if note_ids.is_empty() {
+            return Err(anyhow::anyhow!("No notes to consume"));
+        }
+        note_ids
+            .iter()
+            .map(|note_id| {
+                miden_objects::note::NoteId::try_from_hex(note_id)
+                    .map_err(|e| anyhow::anyhow!("Invalid note id '{}': {}", note_id, e))
+            })
+            .collect()
+    }
+
     /// Flush all cached clients
     /// Returns the number of clients that were flushed
     pub fn flush(&mut self) -> usize {
         let count = self.clients.len();
         self.clients.clear();
+        self.syncs.clear();
         count
     }
 
@@ -1317,6 +2517,8 @@ impl Serve {
         let before = self.clients.len();
         self.clients
             .retain(|(entry_secret, _), _| entry_secret != &secret);
+        self.syncs
+            .retain(|(entry_secret, _), _| entry_secret != &secret);
         before - self.clients.len()
     }
 }
@@ -1346,6 +2548,24 @@ impl std::error::Error for ServeError {}
 mod tests {
     use super::*;
 
+    #[test]
+    fn account_id_on_checks_network() {
+        use miden_objects::address::{AccountIdAddress, Address, AddressInterface};
+        use miden_objects::testing::account_id::ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET;
+
+        let faucet =
+            miden_objects::account::AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap();
+        let address = Address::from(AccountIdAddress::new(faucet, AddressInterface::Unspecified));
+        let testnet = address.to_bech32(Network::Testnet.to_network_id());
+
+        assert_eq!(
+            Serve::account_id_on(&testnet, Network::Testnet).unwrap(),
+            faucet
+        );
+        assert!(Serve::account_id_on(&testnet, Network::Localnet).is_err());
+        assert!(Serve::account_id_on("not an account", Network::Testnet).is_err());
+    }
+
     #[test]
     fn record_account_order_persists_entry() {
         let store = mosaic_miden::store::Store::new(":memory:").expect("store");
@@ -1368,4 +2588,292 @@ mod tests {
         assert_eq!(order.status, "succeeded");
         assert_eq!(order.account, "test_account");
     }
+
+    #[test]
+    fn desk_status_persists_in_desk_store() {
+        let desk_store = DeskStore::new(":memory:").expect("desk store");
+        let market = Market {
+            base: mosaic_fi::Currency {
+                code: "BTC".to_string(),
+                issuer: "base".to_string(),
+            },
+            quote: mosaic_fi::Currency {
+                code: "USDC".to_string(),
+                issuer: "quote".to_string(),
+            },
+        };
+        desk_store
+            .insert_desk(
+                "desk_account",
+                "owner",
+                "owner_account",
+                Path::new("desk"),
+                Network::Testnet,
+                &market,
+                "/desk/desk_account",
+            )
+            .expect("insert desk");
+        assert_eq!(desk_store.get_desk_status("desk_account").unwrap(), None);
+
+        desk_store
+            .update_desk_status("desk_account", DeskStatus::Open)
+            .expect("status updated");
+        desk_store
+            .update_desk_status("desk_account", DeskStatus::Closed)
+            .expect("status updated");
+        assert_eq!(
+            desk_store.get_desk_status("desk_account").unwrap(),
+            Some(DeskStatus::Closed)
+        );
+        assert!(
+            desk_store
+                .update_desk_status("missing", DeskStatus::Open)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn quote_request_is_settled_once_at_offered_price() {
+        use miden_objects::address::{AccountIdAddress, Address, AddressInterface};
+        use miden_objects::testing::account_id::{
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
+        };
+
+        let desk = miden_objects::account::AccountId::try_from(
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
+        )
+        .unwrap();
+        let client = miden_objects::account::AccountId::try_from(
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        )
+        .unwrap();
+        let market = Market {
+            base: mosaic_fi::Currency {
+                code: "BTC".to_string(),
+                issuer: "base".to_string(),
+            },
+            quote: mosaic_fi::Currency {
+                code: "USDC".to_string(),
+                issuer: "quote".to_string(),
+            },
+        };
+        let offer = Order::QuoteRequestOffer {
+            market: market.clone(),
+            uuid: 1,
+            side: Side::BUY,
+            amount: Amount::new(250),
+            price: Price::new(65_000),
+            target_account_id: Address::from(AccountIdAddress::new(
+                client,
+                AddressInterface::Unspecified,
+            ))
+            .to_bech32(Network::Testnet.to_network_id()),
+        };
+
+        // Only the offered price settles the request
+        assert!(Serve::check_settlement_price(Some(&offer), Price::new(65_000)).is_ok());
+        assert!(Serve::check_settlement_price(Some(&offer), Price::new(64_000)).is_err());
+        assert!(Serve::check_settlement_price(None, Price::new(65_000)).is_err());
+        let declined = Order::QuoteRequestNoOffer {
+            market,
+            uuid: 1,
+            target_account_id: String::new(),
+        };
+        assert!(Serve::check_settlement_price(Some(&declined), Price::new(65_000)).is_err());
+
+        let desk_note_store = desk_store::DeskNoteStore::new(":memory:").expect("desk note store");
+        let response = mosaic_fi::note::compile_note_from_account_id(desk, offer).unwrap();
+        assert!(
+            desk_note_store
+                .insert_response(7, ResponseKind::Quote, &response)
+                .unwrap()
+        );
+        assert!(
+            !desk_note_store
+                .insert_response(7, ResponseKind::Quote, &response)
+                .unwrap()
+        );
+        assert_eq!(
+            desk_note_store
+                .get_response(7, ResponseKind::Quote)
+                .unwrap()
+                .map(|response| response.order),
+            Some(response.order.clone())
+        );
+
+        // A settlement that could not be sent can be retried
+        assert!(
+            desk_note_store
+                .insert_response(7, ResponseKind::Settlement, &response)
+                .unwrap()
+        );
+        desk_note_store
+            .delete_response(7, ResponseKind::Settlement)
+            .unwrap();
+        assert!(
+            desk_note_store
+                .get_response(7, ResponseKind::Settlement)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            desk_note_store
+                .insert_response(7, ResponseKind::Settlement, &response)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn limit_order_is_acknowledged_once() {
+        use miden_objects::address::{AccountIdAddress, Address, AddressInterface};
+        use miden_objects::testing::account_id::{
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
+        };
+
+        let desk = miden_objects::account::AccountId::try_from(
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
+        )
+        .unwrap();
+        let client = miden_objects::account::AccountId::try_from(
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        )
+        .unwrap();
+        let market = Market {
+            base: mosaic_fi::Currency {
+                code: "BTC".to_string(),
+                issuer: "base".to_string(),
+            },
+            quote: mosaic_fi::Currency {
+                code: "USDC".to_string(),
+                issuer: "quote".to_string(),
+            },
+        };
+        let ack = Order::LimitBuyOrderLocked {
+            market,
+            uuid: 1,
+            target_account_id: Address::from(AccountIdAddress::new(
+                client,
+                AddressInterface::Unspecified,
+            ))
+            .to_bech32(Network::Testnet.to_network_id()),
+        };
+
+        let desk_note_store = desk_store::DeskNoteStore::new(":memory:").expect("desk note store");
+        let response = mosaic_fi::note::compile_note_from_account_id(desk, ack).unwrap();
+        assert!(
+            desk_note_store
+                .insert_response(9, ResponseKind::LimitOrderAck, &response)
+                .unwrap()
+        );
+        assert!(
+            !desk_note_store
+                .insert_response(9, ResponseKind::LimitOrderAck, &response)
+                .unwrap()
+        );
+
+        // An acknowledgement that could not be sent can be retried
+        desk_note_store
+            .delete_response(9, ResponseKind::LimitOrderAck)
+            .unwrap();
+        assert!(
+            desk_note_store
+                .insert_response(9, ResponseKind::LimitOrderAck, &response)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn consumed_note_ids_are_parsed_from_hex() {
+        let note_id = miden_objects::note::NoteId::from(miden_objects::Word::from([1u32, 2, 3, 4]));
+        assert_eq!(
+            Serve::parse_note_ids(&[note_id.to_hex()]).unwrap(),
+            vec![note_id]
+        );
+
+        let err = Serve::parse_note_ids(&[note_id.to_hex(), "0xnot-hex".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("Invalid note id '0xnot-hex'"));
+        assert!(Serve::parse_note_ids(&["0x01".to_string()]).is_err());
+        assert_eq!(
+            Serve::parse_note_ids(&[]).unwrap_err().to_string(),
+            "No notes to consume"
+        );
+    }
+
+    #[test]
+    fn pushed_notes_record_fills_without_fees_when_scale_is_unknown() {
+        use miden_objects::testing::account_id::ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE;
+
+        let client = miden_objects::account::AccountId::try_from(
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        )
+        .unwrap();
+        let market = Market {
+            base: mosaic_fi::Currency {
+                code: "BTC".to_string(),
+                issuer: "base".to_string(),
+            },
+            quote: mosaic_fi::Currency {
+                code: "USDC".to_string(),
+                issuer: "quote".to_string(),
+            },
+        };
+        let request = Order::QuoteRequest {
+            market,
+            uuid: 1,
+            side: Side::BUY,
+            amount: Amount::new(300),
+        };
+        let note = mosaic_fi::note::compile_note_from_account_id(client, request).unwrap();
+
+        let desk_note_store = desk_store::DeskNoteStore::new(":memory:").expect("desk note store");
+        let note_id = desk_note_store.insert_note(&note, NoteStatus::New).unwrap();
+        desk_note_store
+            .update_note_status(note_id, NoteStatus::Consumed)
+            .unwrap();
+
+        let fill = Fill {
+            id: 1,
+            side: Side::BUY,
+            maker_id: 1,
+            price: Price::new(1_000),
+            amount: Amount::new(300),
+            taker_uuid: 1,
+            maker_uuid: 2,
+            maker_account: String::new(),
+            taker_account: None,
+            fees: None,
+            maker_fee: 0,
+            taker_fee: 0,
+            decimal_price: None,
+            decimal_amount: None,
+        };
+        let fees = FeeSchedule {
+            maker_bps: 10,
+            taker_bps: 25,
+        };
+
+        // Without the market decimals the fill is recorded, without fees
+        let mut fills = vec![fill.clone()];
+        Serve::store_desk_fills(&desk_note_store, note_id, &mut fills, fees, None).unwrap();
+        assert_eq!((fills[0].maker_fee, fills[0].taker_fee), (0, 0));
+
+        let mut fills = vec![Fill { id: 2, ..fill }];
+        Serve::store_desk_fills(
+            &desk_note_store,
+            note_id,
+            &mut fills,
+            fees,
+            Some(Scale::default()),
+        )
+        .unwrap();
+        assert_eq!((fills[0].maker_fee, fills[0].taker_fee), (300, 750));
+
+        let stored = desk_note_store.get_fills().unwrap();
+        assert_eq!(stored.len(), 2);
+        assert!(stored.iter().all(|(id, _)| *id == note_id));
+        assert_eq!(stored[0].1.taker_fee, 0);
+        assert_eq!(stored[1].1.taker_fee, 750);
+    }
 }
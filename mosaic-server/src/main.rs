@@ -1,5 +1,5 @@
 use axum::{
-    Router,
+    Extension, Router,
     body::Body,
     extract::{Json, Path, State as AxumState},
     http::{HeaderMap, HeaderName, HeaderValue, Request, StatusCode},
@@ -10,14 +10,16 @@ use axum::{
 use clap::Parser;
 use mosaic_fi::note::MosaicNote;
 use mosaic_mcp::Mosaic;
-use mosaic_miden::Network;
+use mosaic_miden::{Network, NetworkConfig};
 use mosaic_serve::{Serve, asset_store::default_assets};
 use rmcp::transport::streamable_http_server::{
     StreamableHttpService, session::local::LocalSessionManager,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tracing::warn;
@@ -100,6 +102,20 @@ async fn preflight_desk_handler() -> impl IntoResponse {
     response
 }
 
+/// Install the network configuration file, such as
+/// `{"Mainnet": {"endpoint": "https://node.example.com:443", "timeout_ms": 10000, "network_id": "mm"}}`
+fn load_network_config(path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read network config '{}': {}", path, e))?;
+    let configs: HashMap<Network, NetworkConfig> = serde_json::from_str(&contents)
+        .map_err(|e| format!("Invalid network config '{}': {}", path, e))?;
+    for (network, config) in &configs {
+        tracing::info!(%network, endpoint = %config.endpoint, "Configured network");
+    }
+    mosaic_miden::configure_networks(configs)?;
+    Ok(())
+}
+
 fn fallback_desk_url(account_id: &str) -> String {
     std::env::var("MOSAIC_SERVER")
         .map(|base| format!("{}/desk/{}", base.trim_end_matches('/'), account_id))
@@ -133,6 +149,14 @@ struct Args {
     /// Disable OAuth authentication (for testing)
     #[arg(long, default_value_t = false)]
     no_auth: bool,
+
+    /// JSON file mapping networks to their node endpoint, timeout and network id
+    #[arg(long)]
+    network_config: Option<String>,
+
+    /// Sync clients in the background every N seconds, consuming incoming notes (0 disables)
+    #[arg(long)]
+    auto_sync_secs: Option<u64>,
 }
 
 // Request/Response types for HTTP API
@@ -151,6 +175,21 @@ struct DeskInfoResponse {
     quote_account: String,
     market_url: String,
     owner_account: String,
+    /// Trading status read from the desk account, when available
+    status: Option<mosaic_fi::book::DeskStatus>,
+    /// Fee schedule read from the desk account, when available
+    fees: Option<mosaic_fi::book::FeeSchedule>,
+    /// Order limits read from the desk account, when available
+    limits: Option<mosaic_fi::book::OrderLimits>,
+}
+
+#[derive(Debug, Serialize)]
+struct DeskBookResponse {
+    desk_account: String,
+    bids: Vec<mosaic_fi::book::PriceLevel>,
+    asks: Vec<mosaic_fi::book::PriceLevel>,
+    /// Decimals to display the levels with, when the desk assets are registered
+    scale: Option<mosaic_fi::note::Scale>,
 }
 
 #[derive(Debug, Serialize)]
@@ -172,6 +211,9 @@ async fn get_desk_info_handler(
 ) -> impl IntoResponse {
     // Get desk info
     let serve = serve.lock().await;
+    let status = serve.desk_get_status(&account_id).await.ok();
+    let fees = serve.desk_get_fees(&account_id).await.ok();
+    let limits = serve.desk_get_limits(&account_id).await.ok();
     match serve.get_desk_info(&account_id).await {
         Ok((account_id, network, market)) => {
             let summary = serve.get_desk_market_summary(&account_id).ok().flatten();
@@ -197,15 +239,15 @@ async fn get_desk_info_handler(
             let response = DeskInfoResponse {
                 desk_account: account_id.clone(),
                 account_id,
-                network: match network {
-                    Network::Testnet => "Testnet".to_string(),
-                    Network::Localnet => "Localnet".to_string(),
-                },
+                network: network.to_string(),
                 market,
                 base_account,
                 quote_account,
                 market_url,
                 owner_account,
+                status,
+                fees,
+                limits,
             };
             let mut response = (StatusCode::OK, Json(response)).into_response();
             apply_desk_cors_headers(response.headers_mut());
@@ -219,6 +261,33 @@ async fn get_desk_info_handler(
     }
 }
 
+// GET /desk/{account_id}/book
+async fn get_desk_book_handler(
+    AxumState(serve): AxumState<Arc<Mutex<Serve>>>,
+    Path(account_id): Path<String>,
+) -> impl IntoResponse {
+    let serve = serve.lock().await;
+    match serve.desk_get_order_book(&account_id).await {
+        Ok(book) => {
+            let scale = serve.desk_scale(&account_id).ok();
+            let response = DeskBookResponse {
+                desk_account: account_id,
+                bids: book.bid_levels(),
+                asks: book.ask_levels(),
+                scale,
+            };
+            let mut response = (StatusCode::OK, Json(response)).into_response();
+            apply_desk_cors_headers(response.headers_mut());
+            response
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to get desk book: {}", e)})),
+        )
+            .into_response(),
+    }
+}
+
 // POST /desk/:account_id/note
 async fn desk_push_note_handler(
     AxumState(serve): AxumState<Arc<Mutex<Serve>>>,
@@ -245,6 +314,57 @@ async fn desk_push_note_handler(
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct FaucetMintRequest {
+    target_account: String,
+    amount: u64,
+}
+
+// POST /faucet/:account_id/mint
+async fn faucet_mint_handler(
+    AxumState(serve): AxumState<Arc<Mutex<Serve>>>,
+    Extension(user_id): Extension<String>,
+    Path(account_id): Path<String>,
+    Json(payload): Json<FaucetMintRequest>,
+) -> impl IntoResponse {
+    // Mint with the faucet of the authenticated user
+    let secret = mosaic_mcp::tools::secret_from_user_id(&user_id);
+    let mut serve = serve.lock().await;
+    match serve
+        .mint(
+            secret,
+            account_id.clone(),
+            payload.target_account,
+            payload.amount.into(),
+        )
+        .await
+    {
+        Ok(note) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "message": "Minted successfully",
+                "faucet_account": account_id,
+                "note": note
+            })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to mint: {}", e)})),
+        )
+            .into_response(),
+    }
+}
+
+/// Faucet routes, always behind OAuth since they act for the user
+fn faucet_routes(serve_state: Arc<Mutex<Serve>>) -> Router {
+    Router::new()
+        .route("/faucet/{account_id}/mint", post(faucet_mint_handler))
+        .layer(middleware::from_fn(oauth::oauth_middleware))
+        .layer(build_cors_layer())
+        .with_state(serve_state)
+}
+
 async fn list_assets_handler() -> impl IntoResponse {
     let assets: Vec<AssetSummary> = default_assets()
         .into_iter()
@@ -262,10 +382,25 @@ async fn list_assets_handler() -> impl IntoResponse {
     (StatusCode::OK, Json(assets))
 }
 
+/// Create the shared Serve instance and restore its desks
+async fn create_serve(
+    storage_path: &str,
+    auto_sync: Option<Duration>,
+) -> Result<Serve, Box<dyn std::error::Error>> {
+    let mut serve = Serve::new(storage_path)?;
+    if let Some(interval) = auto_sync {
+        tracing::info!("Syncing clients in the background every {:?}", interval);
+        serve.enable_auto_sync(interval);
+    }
+    serve.init_desks().await?;
+    Ok(serve)
+}
+
 async fn run_mcp_server(
     port: u16,
     storage_path: String,
     no_auth: bool,
+    auto_sync: Option<Duration>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let bind_address = format!("127.0.0.1:{}", port);
     tracing::info!("Starting MCP server on {}", bind_address);
@@ -278,8 +413,7 @@ async fn run_mcp_server(
     }
 
     // Create shared Serve instance
-    let mut serve = mosaic_serve::Serve::new(&storage_path)?;
-    serve.init_desks().await?;
+    let serve = create_serve(&storage_path, auto_sync).await?;
     let serve_state = Arc::new(Mutex::new(serve));
 
     // Create MCP service
@@ -322,6 +456,10 @@ async fn run_mcp_server(
             "/desk/{account_id}",
             get(get_desk_info_handler).options(preflight_desk_handler),
         )
+        .route(
+            "/desk/{account_id}/book",
+            get(get_desk_book_handler).options(preflight_desk_handler),
+        )
         .route(
             "/desk/{account_id}/note",
             post(desk_push_note_handler).options(preflight_desk_handler),
@@ -338,7 +476,8 @@ async fn run_mcp_server(
         .merge(mcp_router)
         .merge(oauth_routes)
         .merge(asset_routes)
-        .merge(desk_routes);
+        .merge(desk_routes)
+        .merge(faucet_routes(serve_state.clone()));
 
     let tcp_listener = tokio::net::TcpListener::bind(&bind_address).await?;
 
@@ -354,14 +493,14 @@ async fn run_mcp_server(
 async fn run_rest_server(
     port: u16,
     storage_path: String,
+    auto_sync: Option<Duration>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
     tracing::info!("Starting REST API server on {}", addr);
     tracing::info!("Using storage path: {}", storage_path);
 
     // Create shared Serve instance
-    let mut serve = mosaic_serve::Serve::new(&storage_path)?;
-    serve.init_desks().await?;
+    let serve = create_serve(&storage_path, auto_sync).await?;
     let serve_state = Arc::new(Mutex::new(serve));
 
     let desk_routes = Router::new()
@@ -369,6 +508,10 @@ async fn run_rest_server(
             "/desk/{account_id}",
             get(get_desk_info_handler).options(preflight_desk_handler),
         )
+        .route(
+            "/desk/{account_id}/book",
+            get(get_desk_book_handler).options(preflight_desk_handler),
+        )
         .route(
             "/desk/{account_id}/note",
             post(desk_push_note_handler).options(preflight_desk_handler),
@@ -378,7 +521,8 @@ async fn run_rest_server(
 
     let app = Router::new()
         .route("/assets", get(list_assets_handler))
-        .merge(desk_routes);
+        .merge(desk_routes)
+        .merge(faucet_routes(serve_state.clone()));
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
 
@@ -395,6 +539,7 @@ async fn run_combined_server_same_port(
     port: u16,
     storage_path: String,
     no_auth: bool,
+    auto_sync: Option<Duration>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let addr = format!("127.0.0.1:{}", port);
     tracing::info!("Starting combined MCP and REST API server on {}", addr);
@@ -407,8 +552,7 @@ async fn run_combined_server_same_port(
     }
 
     // Create shared Serve instance
-    let mut serve = mosaic_serve::Serve::new(&storage_path)?;
-    serve.init_desks().await?;
+    let serve = create_serve(&storage_path, auto_sync).await?;
     let serve_state = Arc::new(Mutex::new(serve));
 
     // Create MCP service with shared Serve instance
@@ -452,6 +596,10 @@ async fn run_combined_server_same_port(
             "/desk/{account_id}",
             get(get_desk_info_handler).options(preflight_desk_handler),
         )
+        .route(
+            "/desk/{account_id}/book",
+            get(get_desk_book_handler).options(preflight_desk_handler),
+        )
         .route(
             "/desk/{account_id}/note",
             post(desk_push_note_handler).options(preflight_desk_handler),
@@ -459,7 +607,9 @@ async fn run_combined_server_same_port(
         .layer(middleware::from_fn(desk_cors_middleware))
         .with_state(serve_state.clone());
 
-    let http_routes = Router::new().merge(desk_routes);
+    let http_routes = Router::new()
+        .merge(desk_routes)
+        .merge(faucet_routes(serve_state.clone()));
 
     let public_routes = Router::new()
         .route("/assets", get(list_assets_handler))
@@ -488,6 +638,7 @@ async fn run_both_servers_different_ports(
     rest_port: u16,
     storage_path: String,
     no_auth: bool,
+    auto_sync: Option<Duration>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!("Starting MCP server on 127.0.0.1:{}", mcp_port);
     tracing::info!("Starting REST API server on 0.0.0.0:{}", rest_port);
@@ -500,8 +651,7 @@ async fn run_both_servers_different_ports(
     }
 
     // Create shared Serve instance for both servers
-    let mut serve = mosaic_serve::Serve::new(&storage_path)?;
-    serve.init_desks().await?;
+    let serve = create_serve(&storage_path, auto_sync).await?;
     let serve_state = Arc::new(Mutex::new(serve));
 
     // Create a cancellation token for graceful shutdown
@@ -582,9 +732,11 @@ async fn run_both_servers_different_ports(
 
             let app = Router::new()
                 .route("/desk/{account_id}", get(get_desk_info_handler))
+                .route("/desk/{account_id}/book", get(get_desk_book_handler))
                 .route("/desk/{account_id}/note", post(desk_push_note_handler))
                 .route("/assets", get(list_assets_handler))
-                .with_state(serve_clone);
+                .with_state(serve_clone.clone())
+                .merge(faucet_routes(serve_clone));
             let listener = tokio::net::TcpListener::bind(addr).await?;
 
             axum::serve(listener, app)
@@ -654,6 +806,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         std::process::exit(1);
     }
 
+    if let Some(path) = &args.network_config {
+        load_network_config(path)?;
+    }
+
+    let auto_sync = args
+        .auto_sync_secs
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs);
+
     // Create storage directory if it doesn't exist
     std::fs::create_dir_all(&args.storage_path)?;
 
@@ -662,18 +823,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     match (args.mcp, args.rest) {
         (true, false) => {
             // MCP server only
-            run_mcp_server(args.mcp_port, args.storage_path, args.no_auth).await?;
+            run_mcp_server(args.mcp_port, args.storage_path, args.no_auth, auto_sync).await?;
         }
         (false, true) => {
             // REST API server only
-            run_rest_server(args.rest_port, args.storage_path).await?;
+            run_rest_server(args.rest_port, args.storage_path, auto_sync).await?;
         }
         (true, true) => {
             // Both servers
             if args.mcp_port == args.rest_port {
                 // Same port: use combined router for efficiency
-                run_combined_server_same_port(args.mcp_port, args.storage_path, args.no_auth)
-                    .await?;
+                run_combined_server_same_port(
+                    args.mcp_port,
+                    args.storage_path,
+                    args.no_auth,
+                    auto_sync,
+                )
+                .await?;
             } else {
                 // Different ports: run two separate servers concurrently
                 run_both_servers_different_ports(
@@ -681,6 +847,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     args.rest_port,
                     args.storage_path,
                     args.no_auth,
+                    auto_sync,
                 )
                 .await?;
             }
@@ -20,7 +20,7 @@ import { use, useCallback, useEffect, useMemo, useState } from 'react'
 import { getOrImportAccount, getDeskInfo } from '@/lib/account'
 import { marketStorage } from '@/lib/market-storage'
 import { callMcpTool, OrderPayload, RoleSettings, StoredOrderSummary } from '@/lib/mcp-tool'
-import type { ClientAccountInfo, NetworkName } from '@/lib/mcp-tool'
+import type { ClientAccountInfo, MarketDescription, NetworkName } from '@/lib/mcp-tool'
 
 const defaultMarket = { price: 1000, change: '+0.00%', positive: true, volume: '$0' }
 
@@ -120,6 +120,15 @@ export default function MarketPage({
 
   const market = defaultMarket
 
+  // Orders reference the desk market by its base and quote faucets
+  const orderMarket = useMemo<MarketDescription>(
+    () => ({
+      base: { code: base, issuer: baseFaucet },
+      quote: { code: quote, issuer: quoteFaucet },
+    }),
+    [base, quote, baseFaucet, quoteFaucet]
+  )
+
   const fetchAccessToken = useCallback(async () => {
     try {
       const response = await fetch('/api/auth/token')
@@ -343,7 +352,7 @@ export default function MarketPage({
     const roundedAmount = Math.round(amountValue)
     const orderPayload: OrderPayload = {
       QuoteRequest: {
-        market: base && quote ? `${base}/${quote}` : marketId,
+        market: orderMarket,
         uuid: Math.floor(Math.random() * 1_000_000_000_000),
         side: requestSide === 'Buy' ? 'BUY' : 'SELL',
         amount: roundedAmount,
@@ -369,6 +378,7 @@ export default function MarketPage({
           account_id: targetAccount.accountId,
           order: orderPayload,
           commit: true,
+          desk_account: marketId,
         },
         token
       )
@@ -427,7 +437,7 @@ export default function MarketPage({
 
     const orderPayload: OrderPayload = {
       LiquidityOffer: {
-        market: base && quote ? `${base}/${quote}` : marketId,
+        market: orderMarket,
         uuid: Math.floor(Math.random() * 1_000_000_000_000),
         side: liquiditySide === 'Buy' ? 'BUY' : 'SELL',
         amount: roundedAmount,
@@ -454,6 +464,7 @@ export default function MarketPage({
           account_id: targetAccount.accountId,
           order: orderPayload,
           commit: true,
+          desk_account: marketId,
         },
         token
       )
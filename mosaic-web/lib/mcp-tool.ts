@@ -1,6 +1,6 @@
 import { callMCPTool, RawCallToolResult } from '@/lib/mcp-client'
 
-export type NetworkName = 'Testnet' | 'Localnet'
+export type NetworkName = 'Testnet' | 'Devnet' | 'Mainnet' | 'Localnet'
 
 type EmptyArgs = Record<string, never>
 
@@ -71,18 +71,64 @@ export type ConsumeNoteResponse = {
   transaction_id: string
 }
 
+export type ConsumableNote = {
+  note_id: string
+  sender?: string | null
+  script_root: string
+  standard?: 'P2ID' | 'P2IDE' | 'SWAP' | null
+  assets: Array<{
+    faucet: string
+    amount: number
+    fungible: boolean
+  }>
+  consumable_after?: number | null
+}
+
+export type ListConsumableNotesResponse = {
+  success: boolean
+  account_id: string
+  notes: ConsumableNote[]
+}
+
+export type MintResponse = {
+  success: boolean
+  note: unknown
+}
+
+export type TransferResponse = {
+  success: boolean
+  note: unknown
+}
+
 export type DeskPushNoteResponse = {
   success: boolean
   desk_account: string
   note_id: number
 }
 
+export type DeskStatus = 'Open' | 'Paused' | 'CancelOnly' | 'Closed'
+
+export type FeeSchedule = {
+  maker_bps: number
+  taker_bps: number
+}
+
+export type OrderLimits = {
+  tick_size: number
+  lot_size: number
+  min_notional: number
+  max_notional: number
+}
+
 export type GetDeskInfoResponse = {
   success: boolean
   desk_account: string
   account_id: string
   network: string
   market: MarketDescription
+  status?: DeskStatus | null
+  fees?: FeeSchedule | null
+  limits?: OrderLimits | null
 }
 
 export type FlushResponse = {
@@ -132,50 +178,77 @@ export type OrderSide = 'BUY' | 'SELL'
 type OrderUuid = string | number
 type OrderAmount = number
 type OrderPrice = number
+type TimeInForce = 'GTC' | 'IOC' | { GTD: { expiry_block: number } }
 
 type QuoteRequestOrder = {
   QuoteRequest: {
-    market: string
+    market: MarketDescription
     uuid: OrderUuid
     side: OrderSide
-    amount: OrderAmount
+    amount?: OrderAmount
   }
 }
 
 type QuoteRequestOfferOrder = {
   QuoteRequestOffer: {
-    market: string
+    market: MarketDescription
     uuid: OrderUuid
     side: OrderSide
     amount: OrderAmount
     price: OrderPrice
+    target_account_id: string
   }
 }
 
 type QuoteRequestNoOfferOrder = {
   QuoteRequestNoOffer: {
-    market: string
+    market: MarketDescription
     uuid: OrderUuid
+    target_account_id: string
   }
 }
 
 type LimitOrder = {
   LimitOrder: {
-    market: string
+    market: MarketDescription
+    uuid: OrderUuid
+    side: OrderSide
+    amount?: OrderAmount
+    price?: OrderPrice
+    time_in_force?: TimeInForce
+  }
+}
+
+type SettleOrder = {
+  Settle: {
+    market: MarketDescription
     uuid: OrderUuid
     side: OrderSide
     amount: OrderAmount
     price: OrderPrice
+    target_account_id: string
+    fee?: number
+    nonce?: number
   }
 }
 
 type LiquidityOfferOrder = {
   LiquidityOffer: {
-    market: string
+    market: MarketDescription
     uuid: OrderUuid
     side: OrderSide
-    amount: OrderAmount
-    price: OrderPrice
+    amount?: OrderAmount
+    price?: OrderPrice
+    time_in_force?: TimeInForce
+  }
+}
+
+type CancelOfferOrder = {
+  CancelOffer: {
+    market: MarketDescription
+    uuid: OrderUuid
+    side: OrderSide
+    entry_id?: number | null
   }
 }
 
@@ -186,17 +259,32 @@ type FundAccountOrder = {
   }
 }
 
+type TransferOrder = {
+  Transfer: {
+    target_account_id: string
+    asset: string
+    amount: OrderAmount
+    reclaim_after?: number | null
+  }
+}
+
 type KycpassedOrder = {
   KYCPassed: {
-    market: string
+    market: MarketDescription
   }
 }
 
-type OrderUnitVariant =
-  | 'LimitBuyOrderLocked'
-  | 'LimitBuyOrderNotLocked'
-  | 'LimitSellOrderLocked'
-  | 'LimitSellOrderNotLocked'
+type LimitOrderAcknowledgement = {
+  market: MarketDescription
+  uuid: OrderUuid
+  target_account_id: string
+}
+
+type LimitOrderAcknowledgementOrder =
+  | { LimitBuyOrderLocked: LimitOrderAcknowledgement }
+  | { LimitBuyOrderNotLocked: LimitOrderAcknowledgement }
+  | { LimitSellOrderLocked: LimitOrderAcknowledgement }
+  | { LimitSellOrderNotLocked: LimitOrderAcknowledgement }
 
 export type OrderPayload =
   | KycpassedOrder
@@ -205,8 +293,11 @@ export type OrderPayload =
   | QuoteRequestOrder
   | LimitOrder
   | LiquidityOfferOrder
+  | CancelOfferOrder
   | FundAccountOrder
-  | OrderUnitVariant
+  | TransferOrder
+  | LimitOrderAcknowledgementOrder
+  | SettleOrder
 
 type AccountOrderCreateClient = {
   CreateClient: {
@@ -220,6 +311,8 @@ type AccountOrderCreateDesk = {
     network: NetworkName
     market: MarketDescription
     owner_account: string
+    fees?: FeeSchedule
+    limits?: Partial<OrderLimits>
   }
 }
 
@@ -325,6 +418,10 @@ export type ToolDefinitions = {
       account_id: string
       order: OrderPayload
       commit?: boolean
+      desk_account?: string | null
+      backed?: boolean
+      decimal_amount?: string | null
+      decimal_price?: string | null
     }
     result: CreateOrderResponse
   }
@@ -367,6 +464,39 @@ export type ToolDefinitions = {
     }
     result: ConsumeNoteResponse
   }
+  list_consumable_notes: {
+    args: {
+      network: NetworkName
+      account_id: string
+    }
+    result: ListConsumableNotesResponse
+  }
+  consume_notes: {
+    args: {
+      network: NetworkName
+      account_id: string
+      note_ids: string[]
+    }
+    result: ConsumeNoteResponse
+  }
+  mint: {
+    args: {
+      faucet_account: string
+      target_account: string
+      amount: number
+    }
+    result: MintResponse
+  }
+  transfer: {
+    args: {
+      account_id: string
+      target_account: string
+      asset: string
+      amount: number
+      reclaim_after?: number | null
+    }
+    result: TransferResponse
+  }
   desk_push_note: {
     args: {
       desk_account: string